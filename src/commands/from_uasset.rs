@@ -1,13 +1,30 @@
 use std::{io::Cursor, num::NonZero};
 
 use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
-use nu_protocol::{Category, Example, LabeledError, PipelineData, Signature, Span, Value, record};
-use uasset::{AssetHeader, NameReference, ObjectImportOuter};
+use nu_protocol::{
+    Category, DataSource, Example, LabeledError, PipelineData, PipelineMetadata, Signature, Span,
+    Value, record,
+};
+use uasset::{AssetHeader, NameReference, ObjectImport};
 
-use crate::UnrealEnginePlugin;
+use crate::{
+    UnrealEnginePlugin,
+    utils::{
+        uasset_reader::{self, ObjectExport, ObjectResolver, PackageName},
+        ue_paths,
+    },
+};
 
 pub struct FromUAsset;
 
+struct FromUAssetOptions {
+    replace_names: bool,
+    resolve_paths: bool,
+    registry: bool,
+    // Package name of the opened file, e.g. /Game/Meshes/SM_Rock
+    package_name: Option<String>,
+}
+
 fn name_reference_serializable(
     name_reference: NameReference,
     span: Span,
@@ -27,11 +44,152 @@ fn name_reference_serializable(
     }
 }
 
-fn convert_asset_header_to_value<R: std::io::Read>(
-    header: &AssetHeader<R>,
+fn package_name_serializable(
+    package_name: PackageName,
     span: Span,
     replace_names: bool,
+    names: &[String],
 ) -> Value {
+    if replace_names {
+        Value::string(package_name.resolve(names), span)
+    } else {
+        Value::record(
+            record! {
+                "index" => Value::int(package_name.index as i64, span),
+                "number" => if package_name.number != 0 { Value::int(package_name.number as i64, span) } else { Value::nothing(span) },
+            },
+            span,
+        )
+    }
+}
+
+fn convert_export_to_value<R: std::io::Read>(
+    export: &ObjectExport,
+    index: i32,
+    resolver: &ObjectResolver<R>,
+    span: Span,
+    options: &FromUAssetOptions,
+) -> Value {
+    let header = resolver.header;
+    let replace_names = options.replace_names;
+    let mut record = record! {
+        "class_index" => Value::int(export.class_index as i64, span),
+        "super_index" => Value::int(export.super_index as i64, span),
+        "template_index" => Value::int(export.template_index as i64, span),
+        "outer_index" => Value::int(export.outer_index as i64, span),
+        "object_name" => package_name_serializable(export.object_name, span, replace_names, &header.names),
+        "object_flags" => Value::int(export.object_flags as i64, span),
+        "serial_size" => Value::int(export.serial_size, span),
+        "serial_offset" => Value::int(export.serial_offset, span),
+        "forced_export" => Value::bool(export.forced_export, span),
+        "not_for_client" => Value::bool(export.not_for_client, span),
+        "not_for_server" => Value::bool(export.not_for_server, span),
+        "is_inherited_instance" => Value::bool(export.is_inherited_instance, span),
+        "package_flags" => Value::int(export.package_flags as i64, span),
+        "not_always_loaded_for_editor_game" => Value::bool(export.not_always_loaded_for_editor_game, span),
+        "is_asset" => Value::bool(export.is_asset, span),
+        "generate_public_hash" => Value::bool(export.generate_public_hash, span),
+    };
+    if replace_names {
+        let resolve = |index: i32| {
+            uasset_reader::resolve_object_name(index, header, resolver.exports)
+                .map(|name| Value::string(name, span))
+                .unwrap_or_default()
+        };
+        record.insert("class_name", resolve(export.class_index));
+        record.insert("super_name", resolve(export.super_index));
+        record.insert("outer_name", resolve(export.outer_index));
+    }
+    if options.resolve_paths {
+        record.insert(
+            "path",
+            resolver
+                .full_path(index)
+                .map(|path| Value::string(path, span))
+                .unwrap_or_default(),
+        );
+    }
+    Value::record(record, span)
+}
+
+fn convert_import_to_value<R: std::io::Read>(
+    import: &ObjectImport,
+    index: i32,
+    resolver: &ObjectResolver<R>,
+    span: Span,
+    options: &FromUAssetOptions,
+) -> Value {
+    let names = &resolver.header.names;
+    let replace_names = options.replace_names;
+    let mut record = record! {
+        "class_package" => name_reference_serializable(import.class_package, span, replace_names, names),
+        "class_name" => name_reference_serializable(import.class_name, span, replace_names, names),
+        "outer_index" => Value::int(uasset_reader::import_outer_index(import) as i64, span),
+        "object_name" => name_reference_serializable(import.object_name, span, replace_names, names),
+        "package_name" => import.package_name.map(|n| name_reference_serializable(n, span, replace_names, names)).unwrap_or_default(),
+        "import_optional" => Value::bool(import.import_optional, span),
+    };
+    if options.resolve_paths {
+        record.insert(
+            "path",
+            resolver
+                .full_path(index)
+                .map(|path| Value::string(path, span))
+                .unwrap_or_default(),
+        );
+    }
+    Value::record(record, span)
+}
+
+// A table that cannot be decoded is null in the header, its error is listed in decode_errors
+fn decode_or_null<T>(
+    table: &str,
+    result: Result<T, LabeledError>,
+    decode_errors: &mut Vec<String>,
+) -> Option<T> {
+    result
+        .map_err(|e| decode_errors.push(format!("{}: {}", table, e.msg)))
+        .ok()
+}
+
+fn convert_asset_header_to_value<R: std::io::Read>(
+    bytes: &[u8],
+    resolver: &ObjectResolver<R>,
+    exports_decoded: bool,
+    mut decode_errors: Vec<String>,
+    span: Span,
+    options: &FromUAssetOptions,
+) -> Value {
+    let header = resolver.header;
+    let exports = if exports_decoded {
+        Value::list(
+            resolver
+                .exports
+                .iter()
+                .enumerate()
+                .map(|(index, e)| {
+                    convert_export_to_value(e, index as i32 + 1, resolver, span, options)
+                })
+                .collect(),
+            span,
+        )
+    } else {
+        Value::nothing(span)
+    };
+    let soft_object_paths = decode_or_null(
+        "soft_object_paths",
+        uasset_reader::read_soft_object_paths(bytes, header),
+        &mut decode_errors,
+    )
+    .map(|paths| Value::list(paths.iter().map(|p| p.to_value(span)).collect(), span))
+    .unwrap_or_default();
+    let soft_package_references = decode_or_null(
+        "soft_package_references",
+        uasset_reader::read_soft_package_references(bytes, header),
+        &mut decode_errors,
+    )
+    .map(|names| Value::list(names.iter().map(|n| Value::string(n, span)).collect(), span))
+    .unwrap_or_default();
     Value::record(
         record! {
             "archive" => Value::record(record! {
@@ -47,30 +205,19 @@ fn convert_asset_header_to_value<R: std::io::Read>(
             "names" => Value::list(header.names.iter().map(|n| Value::string(n, span)).collect::<Vec<_>>(), span),
             "soft_object_paths_count" => Value::int(header.soft_object_paths_count as i64, span),
             "soft_object_paths_offset" => Value::int(header.soft_object_paths_offset as i64, span),
+            "soft_object_paths" => soft_object_paths,
             "localization_id" => header.localization_id.clone().map(|s| Value::string(s, span)).unwrap_or_default(),
             "gatherable_text_data_count" => Value::int(header.gatherable_text_data_count as i64, span),
             "gatherable_text_data_offset" => Value::int(header.gatherable_text_data_offset as i64, span),
             "export_count" => Value::int(header.export_count as i64, span),
             "export_offset" => Value::int(header.export_offset as i64, span),
-            "imports" => Value::list(header.imports.iter().map(|i|
-                Value::record(record!{
-                "class_package" => name_reference_serializable(i.class_package, span, replace_names, &header.names),
-                "class_name" => name_reference_serializable(i.class_name, span, replace_names, &header.names),
-                "outer_index" => Value::int(match i.outer() {
-                    // Reverse https://github.com/jorgenpt/uasset-rs/blob/8b6ecff0c0b19c2a7e0556375742ed90095ff881/src/lib.rs#L171-L178
-                    // Same as https://github.com/EpicGames/UnrealEngine/blob/2d53fcab0066b1f16dd956b227720841cad0f6f7/Engine/Source/Runtime/CoreUObject/Public/UObject/ObjectResource.h#L90-L101
-                    ObjectImportOuter::Root => 0,
-                    ObjectImportOuter::Import { import_index } => -(import_index as i64) - 1,
-                    ObjectImportOuter::Export { export_index } => (export_index as i64) + 1,
-                }, span),
-                "object_name" => name_reference_serializable(i.object_name, span, replace_names, &header.names),
-                "package_name" => i.package_name.map(|n| name_reference_serializable(n, span, replace_names, &header.names)).unwrap_or_default(),
-                "import_optional" => Value::bool(i.import_optional, span),
-            }, span)).collect::<Vec<_>>(), span),
+            "exports" => exports,
+            "imports" => Value::list(header.imports.iter().enumerate().map(|(index, i)| convert_import_to_value(i, -(index as i32) - 1, resolver, span, options)).collect::<Vec<_>>(), span),
 
             "depends_offset" => Value::int(header.depends_offset as i64, span),
             "soft_package_references_count" => Value::int(header.soft_package_references_count as i64, span),
             "soft_package_references_offset" => Value::int(header.soft_package_references_offset as i64, span),
+            "soft_package_references" => soft_package_references,
             "searchable_names_offset" => header.searchable_names_offset.map(|n| Value::int(n as i64, span)).unwrap_or_default(),
             "thumbnail_table_offset" => Value::int(header.thumbnail_table_offset as i64, span),
             "engine_version" => Value::record(record!{
@@ -102,6 +249,7 @@ fn convert_asset_header_to_value<R: std::io::Read>(
             "names_referenced_from_export_data_count" => Value::int(header.names_referenced_from_export_data_count as i64, span),
             "payload_toc_offset" => Value::int(header.payload_toc_offset as i64, span),
             "data_resource_offset" => header.data_resource_offset.map(|n| Value::int(n as i64, span)).unwrap_or_default(),
+            "decode_errors" => Value::list(decode_errors.iter().map(|e| Value::string(e, span)).collect::<Vec<_>>(), span),
         },
         span,
     )
@@ -110,12 +258,48 @@ fn convert_asset_header_to_value<R: std::io::Read>(
 fn from_uasset_header(
     bytes: &[u8],
     span: Span,
-    replace_names: bool,
-) -> Result<Value, uasset::Error> {
+    options: &FromUAssetOptions,
+) -> Result<Value, LabeledError> {
     let cursor = Cursor::new(bytes);
-    let asset_header = AssetHeader::new(cursor)?;
-    let value = convert_asset_header_to_value(&asset_header, span, replace_names);
-    Ok(value)
+    let asset_header = AssetHeader::new(cursor).map_err(|e| LabeledError::new(e.to_string()))?;
+    if options.registry {
+        let entries = uasset_reader::read_asset_registry(
+            bytes,
+            &asset_header,
+            options.package_name.as_deref(),
+        )?;
+        return Ok(Value::list(
+            entries.iter().map(|entry| entry.to_value(span)).collect(),
+            span,
+        ));
+    }
+    let mut decode_errors = vec![];
+    let exports = decode_or_null(
+        "exports",
+        uasset_reader::read_exports(bytes, &asset_header),
+        &mut decode_errors,
+    );
+    let resolver = ObjectResolver {
+        header: &asset_header,
+        exports: exports.as_deref().unwrap_or_default(),
+        package_name: options.package_name.as_deref(),
+    };
+    Ok(convert_asset_header_to_value(
+        bytes,
+        &resolver,
+        exports.is_some(),
+        decode_errors,
+        span,
+        options,
+    ))
+}
+
+// Get the package name from the path of the opened file
+fn package_name_from_metadata(meta: &Option<PipelineMetadata>) -> Option<String> {
+    match &meta.as_ref()?.data_source {
+        DataSource::FilePath(path) => ue_paths::get_package_name(path),
+        _ => None,
+    }
 }
 
 impl PluginCommand for FromUAsset {
@@ -136,6 +320,16 @@ impl PluginCommand for FromUAsset {
                 "Replace index by the conrresponding name",
                 None,
             )
+            .switch(
+                "resolve-paths",
+                "Add the full path of imports and exports, e.g. /Script/Engine.StaticMesh'/Game/Meshes/SM_Rock.SM_Rock'",
+                None,
+            )
+            .switch(
+                "registry",
+                "Return the assets of the asset registry data {object_path, class, tags} instead of the header",
+                None,
+            )
             .category(Category::Conversions)
     }
 
@@ -151,6 +345,16 @@ impl PluginCommand for FromUAsset {
                 description: "Open a uasset file and return its header as a record",
                 result: None,
             },
+            Example {
+                example: "open BP_ThirdPersonCharacter.uasset --raw | from uasset --resolve-paths | get imports.path",
+                description: "List the full path of the imported objects",
+                result: None,
+            },
+            Example {
+                example: "open BP_ThirdPersonCharacter.uasset --raw | from uasset --registry | get tags.ParentClass",
+                description: "Get the parent class of a Blueprint from the asset registry tags",
+                result: None,
+            },
         ]
     }
 
@@ -161,7 +365,12 @@ impl PluginCommand for FromUAsset {
         call: &EvaluatedCall,
         input: PipelineData,
     ) -> Result<PipelineData, LabeledError> {
-        let replace_names = call.has_flag("replace-names")?;
+        let mut options = FromUAssetOptions {
+            replace_names: call.has_flag("replace-names")?,
+            resolve_paths: call.has_flag("resolve-paths")?,
+            registry: call.has_flag("registry")?,
+            package_name: None,
+        };
         match input {
             PipelineData::Empty => Ok(PipelineData::Empty),
             PipelineData::Value(v, meta) => match v {
@@ -169,8 +378,8 @@ impl PluginCommand for FromUAsset {
                     val,
                     internal_span: span,
                 } => {
-                    let value = from_uasset_header(&val, span, replace_names)
-                        .map_err(|e| LabeledError::new(e.to_string()))?;
+                    options.package_name = package_name_from_metadata(&meta);
+                    let value = from_uasset_header(&val, span, &options)?;
                     Ok(PipelineData::Value(value, meta))
                 }
                 v => Err(LabeledError::new(format!(
@@ -186,8 +395,8 @@ impl PluginCommand for FromUAsset {
                         val,
                         internal_span: span,
                     } => {
-                        let value = from_uasset_header(&val, span, replace_names)
-                            .map_err(|e| LabeledError::new(e.to_string()))?;
+                        options.package_name = package_name_from_metadata(&meta);
+                        let value = from_uasset_header(&val, span, &options)?;
                         Ok(PipelineData::Value(value, meta))
                     }
                     _ => unreachable!(),
@@ -206,3 +415,133 @@ fn test_examples() -> Result<(), nu_protocol::ShellError> {
 
     PluginTest::new("unreal_engine", UnrealEnginePlugin.into())?.test_command_examples(&FromUAsset)
 }
+
+// Decode SM_Rock with a table claiming more entries than the package contains.
+// The table is null and reported in decode_errors, the other tables are still decoded.
+#[cfg(test)]
+fn from_uasset_with_invalid_table(
+    table: &str,
+    get_table: impl Fn(&AssetHeader<Cursor<&[u8]>>) -> [[u8; 4]; 2],
+    options: &FromUAssetOptions,
+) -> nu_protocol::Record {
+    let bytes = include_bytes!("../../tests/fixtures/Content/Meshes/SM_Rock.uasset");
+    let header = AssetHeader::new(Cursor::new(bytes.as_slice())).unwrap();
+
+    // The count of the table is followed by its offset in the summary
+    let mut bytes = bytes.to_vec();
+    let count_and_offset = get_table(&header).concat();
+    let position = bytes
+        .windows(8)
+        .position(|w| w == count_and_offset)
+        .unwrap();
+    bytes[position..position + 4].copy_from_slice(&1000i32.to_le_bytes());
+
+    let value = from_uasset_header(&bytes, Span::test_data(), options).unwrap();
+    let record = value.as_record().unwrap().clone();
+    assert!(record.get(table).unwrap().is_nothing());
+    let decode_errors = record.get("decode_errors").unwrap().as_list().unwrap();
+    assert_eq!(decode_errors.len(), 1);
+    assert!(
+        decode_errors[0]
+            .as_str()
+            .unwrap()
+            .starts_with(&format!("{}: ", table))
+    );
+    record
+}
+
+#[test]
+fn test_from_uasset_invalid_exports() {
+    let options = FromUAssetOptions {
+        replace_names: true,
+        resolve_paths: true,
+        registry: false,
+        package_name: Some("/Game/Meshes/SM_Rock".to_string()),
+    };
+    let record = from_uasset_with_invalid_table(
+        "exports",
+        |header| {
+            [
+                header.export_count.to_le_bytes(),
+                header.export_offset.to_le_bytes(),
+            ]
+        },
+        &options,
+    );
+    let imports = record.get("imports").unwrap().as_list().unwrap();
+    assert_eq!(imports.len(), 6);
+    assert_eq!(
+        imports[4]
+            .get_data_by_key("path")
+            .unwrap()
+            .as_str()
+            .unwrap(),
+        "/Script/Engine.Material'/Game/Materials/M_Rock.M_Rock'"
+    );
+}
+
+#[test]
+fn test_from_uasset_invalid_soft_object_paths() {
+    let options = FromUAssetOptions {
+        replace_names: false,
+        resolve_paths: false,
+        registry: false,
+        package_name: None,
+    };
+    let record = from_uasset_with_invalid_table(
+        "soft_object_paths",
+        |header| {
+            [
+                header.soft_object_paths_count.to_le_bytes(),
+                header.soft_object_paths_offset.to_le_bytes(),
+            ]
+        },
+        &options,
+    );
+    assert_eq!(record.get("exports").unwrap().as_list().unwrap().len(), 3);
+    let soft_package_references = record.get("soft_package_references").unwrap();
+    assert_eq!(
+        soft_package_references.as_list().unwrap()[0]
+            .as_str()
+            .unwrap(),
+        "/Game/Textures/T_Rock"
+    );
+}
+
+#[test]
+#[ignore = "needs an engine installation in UNREAL_ENGINE_PATH"]
+fn test_from_uasset_engine_package() {
+    // A package saved by the engine, the fixtures are generated by tests/fixtures/generate_packages.py
+    let path = std::path::PathBuf::from(std::env::var("UNREAL_ENGINE_PATH").unwrap())
+        .join("Engine/Content/BasicShapes/Cube.uasset");
+    let bytes = std::fs::read(&path).unwrap();
+    let options = FromUAssetOptions {
+        replace_names: true,
+        resolve_paths: true,
+        registry: true,
+        package_name: ue_paths::get_package_name(&path),
+    };
+    assert_eq!(
+        options.package_name.as_deref(),
+        Some("/Engine/BasicShapes/Cube")
+    );
+
+    let value = from_uasset_header(&bytes, Span::test_data(), &options).unwrap();
+    let record = value.as_record().unwrap();
+    assert!(
+        record
+            .get("decode_errors")
+            .unwrap()
+            .as_list()
+            .unwrap()
+            .is_empty()
+    );
+    let exports = record.get("exports").unwrap().as_list().unwrap();
+    assert!(exports.iter().any(|export| {
+        export.get_data_by_key("path").is_some_and(|path| {
+            path.as_str().is_ok_and(|path| {
+                path == "/Script/Engine.StaticMesh'/Engine/BasicShapes/Cube.Cube'"
+            })
+        })
+    }));
+}
@@ -0,0 +1,51 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Value};
+
+use crate::{UnrealEnginePlugin, utils::ue_ini::IniFile};
+
+pub struct FromUEIni;
+
+impl SimplePluginCommand for FromUEIni {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "from ueini"
+    }
+
+    fn description(&self) -> &str {
+        "Parse an Unreal Engine config file into a record of sections, each section is a list of {operator, key, value}"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name()).category(Category::Conversions)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "open --raw Config/DefaultGame.ini | from ueini",
+                description: "Parse a config file of the project",
+                result: None,
+            },
+            Example {
+                example: "open --raw Config/DefaultGame.ini | from ueini | get /Script/UnrealEd.ProjectPackagingSettings | where key == MapsToCook | get value.FilePath",
+                description: "List the maps to cook",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        _engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let content = input.as_str().map_err(|_| {
+            LabeledError::new(format!("requires string input, got {}", input.get_type()))
+                .with_label("Expected a string", input.span())
+        })?;
+        Ok(IniFile::parse(content).to_value(call.head))
+    }
+}
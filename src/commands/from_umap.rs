@@ -0,0 +1,86 @@
+use std::path::PathBuf;
+
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{
+    Category, DataSource, Example, LabeledError, PipelineData, PipelineMetadata, Signature, Value,
+};
+
+use crate::{UnrealEnginePlugin, utils::level};
+
+pub struct FromUMap;
+
+// Get the path of the opened file to find its external actors
+fn map_path_from_metadata(meta: &Option<PipelineMetadata>) -> Option<PathBuf> {
+    match &meta.as_ref()?.data_source {
+        DataSource::FilePath(path) => Some(path.clone()),
+        _ => None,
+    }
+}
+
+impl PluginCommand for FromUMap {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "from umap"
+    }
+
+    fn description(&self) -> &str {
+        "Open a umap file and return its actors {name, class, outer, package, external}, including the external actors of World Partition"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name()).category(Category::Conversions)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "open Content/Maps/Main.umap",
+                description: "List the actors of a map",
+                result: None,
+            },
+            Example {
+                example: "open Content/Maps/Main.umap | where class == /Script/Engine.StaticMeshActor",
+                description: "List the static mesh actors of a map",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        _engine: &EngineInterface,
+        _call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let (bytes, span, meta) = match input {
+            PipelineData::Empty => return Ok(PipelineData::Empty),
+            PipelineData::Value(Value::Binary { val, internal_span }, meta) => {
+                (val, internal_span, meta)
+            }
+            PipelineData::Value(v, _) => {
+                return Err(LabeledError::new(format!(
+                    "requires binary input, got {}",
+                    v.get_type()
+                )));
+            }
+            PipelineData::ListStream(_, _) => {
+                return Err(LabeledError::new("unsupported list stream"));
+            }
+            PipelineData::ByteStream(stream, meta) => {
+                let span = stream.span();
+                (stream.into_bytes()?, span, meta)
+            }
+        };
+
+        let actors = level::read_map_actors(&bytes, map_path_from_metadata(&meta).as_ref())?;
+        Ok(PipelineData::Value(
+            Value::list(
+                actors.iter().map(|actor| actor.to_value(span)).collect(),
+                span,
+            ),
+            meta,
+        ))
+    }
+}
@@ -0,0 +1,57 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Value};
+
+use crate::{UnrealEnginePlugin, utils::uplugin::PluginDescriptor};
+
+pub struct FromUPlugin;
+
+impl SimplePluginCommand for FromUPlugin {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "from uplugin"
+    }
+
+    fn description(&self) -> &str {
+        "Parse an Unreal Engine plugin descriptor (.uplugin) into a record with its modules and plugin dependencies"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name()).category(Category::Conversions)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "open Plugins/MyPlugin/MyPlugin.uplugin",
+                description: "Parse a plugin descriptor, `open` uses `from uplugin` for .uplugin files",
+                result: None,
+            },
+            Example {
+                example: "open Plugins/MyPlugin/MyPlugin.uplugin | get modules | where type == Editor",
+                description: "List the editor modules of a plugin",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        _engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let content = input.as_str().map_err(|_| {
+            LabeledError::new(format!("requires string input, got {}", input.get_type()))
+                .with_label("Expected a string", input.span())
+        })?;
+        let json = serde_json::from_str(content.trim_start_matches('\u{feff}')).map_err(|e| {
+            LabeledError::new(format!("Failed to parse the plugin descriptor: {}", e))
+                .with_label("Invalid JSON", input.span())
+        })?;
+        let descriptor = PluginDescriptor::from_json(&json)
+            .map_err(|e| e.with_label("Invalid plugin descriptor", input.span()))?;
+        Ok(descriptor.to_value(call.head))
+    }
+}
@@ -1,15 +1,48 @@
 mod from_uasset;
+mod from_ueini;
+mod from_umap;
+mod from_uplugin;
 mod run_uat;
+mod to_ueini;
 mod ue;
+mod ue_asset_deps;
+mod ue_asset_diff;
 mod ue_build;
+mod ue_config;
+mod ue_engines;
+mod ue_level_actors;
+mod ue_modules;
+mod ue_plugin_info;
+mod ue_plugins;
+mod ue_redirectors;
 mod ue_start;
+mod ue_targets;
+mod ue_unused_assets;
 mod ue_version_selector;
 
 pub use from_uasset::FromUAsset;
+pub use from_ueini::FromUEIni;
+pub use from_umap::FromUMap;
+pub use from_uplugin::FromUPlugin;
 pub use run_uat::UERunUAT;
+pub use to_ueini::ToUEIni;
 pub use ue::UE;
+pub use ue_asset_deps::UEAssetDeps;
+pub use ue_asset_diff::UEAssetDiff;
 pub use ue_build::UEBuild;
+pub use ue_config::UEConfig;
+pub use ue_config::UEConfigSet;
+pub use ue_engines::UEEngines;
+pub use ue_level_actors::UELevelActors;
+pub use ue_modules::UEModules;
+pub use ue_plugin_info::UEPluginInfo;
+pub use ue_plugins::UEPlugins;
+pub use ue_plugins::UEPluginsDisable;
+pub use ue_plugins::UEPluginsEnable;
+pub use ue_redirectors::UERedirectors;
 pub use ue_start::UEStart;
+pub use ue_targets::UETargets;
+pub use ue_unused_assets::UEUnusedAssets;
 pub use ue_version_selector::UEGenerateProjectFiles;
 pub use ue_version_selector::UERegisterEngine;
 pub use ue_version_selector::UESwitchVersion;
@@ -32,6 +32,16 @@ impl PluginCommand for UERunUAT {
                 "Path to a uproject, default is the .uproject file of the current directory",
                 Some('u'),
             )
+            .switch(
+                "stream",
+                "Stream each output line as a record {line, stream, timestamp}",
+                None,
+            )
+            .switch(
+                "no-fail",
+                "Return a record {exit_code, stdout, stderr, duration} instead of failing on a non-zero exit code",
+                None,
+            )
             .allows_unknown_args()
             .category(Category::Plugin)
     }
@@ -58,7 +68,7 @@ impl PluginCommand for UERunUAT {
         let command_name: Spanned<String> = call.req(0)?;
         let args: Vec<String> = call.rest(1)?;
 
-        let unreal_uat_path = ue_paths::get_uat_path(uproject.get_unreal_engine_path_str()?);
+        let unreal_uat_path = ue_paths::get_uat_path(uproject.get_unreal_engine_path_str()?)?;
         let mut command = Command::new(&unreal_uat_path);
 
         command
@@ -70,6 +80,6 @@ impl PluginCommand for UERunUAT {
         command.args(&args);
 
         // Execute the command and return the output
-        ue_tools::run(&mut command, call.head)
+        ue_tools::run_from_call(&mut command, engine, call)
     }
 }
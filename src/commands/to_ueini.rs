@@ -0,0 +1,50 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Value};
+
+use crate::{UnrealEnginePlugin, utils::ue_ini::IniFile};
+
+pub struct ToUEIni;
+
+impl SimplePluginCommand for ToUEIni {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "to ueini"
+    }
+
+    fn description(&self) -> &str {
+        "Convert a record of sections into an Unreal Engine config file, the reverse of `from ueini`. Strings in structs and arrays are quoted only when needed"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name()).category(Category::Conversions)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "open --raw Config/DefaultGame.ini | from ueini | to ueini",
+                description: "Parse and write back a config file",
+                result: None,
+            },
+            Example {
+                example: "{'/Script/EngineSettings.GeneralProjectSettings': {ProjectVersion: 1.2.0}} | to ueini",
+                description: "Write a section from a record of keys and values",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        _engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        Ok(Value::string(
+            IniFile::from_value(input)?.to_string(),
+            call.head,
+        ))
+    }
+}
@@ -45,12 +45,20 @@ impl SimplePluginCommand for UE {
         let uproject_path =
             uproject::uproject_from_arg_or_current_dir(&engine, call.get_flag("uproject")?)?;
         let uproject = uproject::UProject::from_path(&uproject_path)?;
+        let descriptor = uproject.get_descriptor()?;
 
         // Output as a record
         let mut record = record! {
             "uproject_path" => Value::string(uproject.uproject_path.display().to_string(), call.head),
-            "name" => Value::string(uproject.name, call.head),
+            "name" => Value::string(&uproject.name, call.head),
             "ide" => Value::string(uproject.ide.to_string(), call.head),
+            "file_version" => Value::int(descriptor.file_version, call.head),
+            "engine_association" => Value::string(&descriptor.engine_association, call.head),
+            "category" => Value::string(&descriptor.category, call.head),
+            "description" => Value::string(&descriptor.description, call.head),
+            "modules" => Value::list(descriptor.modules.iter().map(|m| m.to_value(call.head)).collect(), call.head),
+            "plugins" => Value::list(descriptor.plugins.iter().map(|p| p.to_value(call.head)).collect(), call.head),
+            "target_platforms" => Value::list(descriptor.target_platforms.iter().map(|p| Value::string(p, call.head)).collect(), call.head),
         };
         if let Some(path) = uproject.unreal_engine_path {
             record.insert(
@@ -58,6 +66,12 @@ impl SimplePluginCommand for UE {
                 Value::string(path.display().to_string(), call.head),
             );
         }
+        if let Some(source) = uproject.unreal_engine_source {
+            record.insert(
+                "unreal_engine_source",
+                Value::string(source.to_string(), call.head),
+            );
+        }
         Ok(Value::record(record, call.head))
     }
 }
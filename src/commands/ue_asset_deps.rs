@@ -0,0 +1,101 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, SyntaxShape, Value, record};
+
+use crate::{
+    UnrealEnginePlugin,
+    utils::{asset_scan, uproject},
+};
+
+pub struct UEAssetDeps;
+
+impl SimplePluginCommand for UEAssetDeps {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "ue asset-deps"
+    }
+
+    fn description(&self) -> &str {
+        "List the package dependencies {from_package, to_package, kind} of the project and plugins Content"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .named(
+                "uproject",
+                SyntaxShape::Filepath,
+                "Path to a uproject, default is the .uproject file of the current directory",
+                Some('u'),
+            )
+            .switch(
+                "script",
+                "Include the dependencies to /Script packages (C++ modules)",
+                None,
+            )
+            .category(Category::Plugin)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "ue asset-deps",
+                description: "List the package dependencies of the project",
+                result: None,
+            },
+            Example {
+                example: "ue asset-deps | where to_package == /Game/Textures/T_Rock",
+                description: "Find the packages depending on a texture",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let uproject_path =
+            uproject::uproject_from_arg_or_current_dir(&engine, call.get_flag("uproject")?)?;
+        let include_script = call.has_flag("script")?;
+
+        let mut edges = vec![];
+        for package in
+            asset_scan::open_package_files(&uproject_path.parent().unwrap().to_path_buf())
+        {
+            // Packages whose dependencies cannot be read are skipped with a warning
+            let dependencies = match package.dependencies() {
+                Ok(dependencies) => dependencies,
+                Err(e) => {
+                    asset_scan::warn_skipped_package(&e);
+                    continue;
+                }
+            };
+            let hard = dependencies
+                .hard
+                .into_iter()
+                .map(|to_package| (to_package, "hard"));
+            let soft = dependencies
+                .soft
+                .into_iter()
+                .map(|to_package| (to_package, "soft"));
+
+            for (to_package, kind) in hard.chain(soft) {
+                if !include_script && to_package.starts_with("/Script/") {
+                    continue;
+                }
+                edges.push(Value::record(
+                    record! {
+                        "from_package" => Value::string(&package.package_name, call.head),
+                        "to_package" => Value::string(to_package, call.head),
+                        "kind" => Value::string(kind, call.head),
+                    },
+                    call.head,
+                ));
+            }
+        }
+        Ok(Value::list(edges, call.head))
+    }
+}
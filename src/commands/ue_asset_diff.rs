@@ -0,0 +1,442 @@
+use std::{collections::BTreeMap, io::Cursor, path::PathBuf};
+
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Span, SyntaxShape, Value, record};
+use uasset::AssetHeader;
+
+use crate::{
+    UnrealEnginePlugin,
+    utils::uasset_reader::{self, ObjectResolver},
+};
+
+pub struct UEAssetDiff;
+
+// Entries of a section by (kind, name) and occurrence, e.g. the dependencies by (hard, /Game/Materials/M_Rock).
+// Duplicate entries are kept and compared in order.
+type Section = BTreeMap<(String, String, usize), String>;
+
+fn to_section(entries: impl IntoIterator<Item = (String, String, String)>) -> Section {
+    let mut section = Section::new();
+    for (kind, name, value) in entries {
+        let mut occurrence = 0;
+        while section.contains_key(&(kind.clone(), name.clone(), occurrence)) {
+            occurrence += 1;
+        }
+        section.insert((kind, name, occurrence), value);
+    }
+    section
+}
+
+/**
+ * Get the bytes of a package from a path or a binary, e.g. from `git show HEAD~1:Content/Map.umap | into binary`
+ */
+fn get_package_bytes(engine: &EngineInterface, value: &Value) -> Result<Vec<u8>, LabeledError> {
+    match value {
+        Value::Binary { val, .. } => Ok(val.clone()),
+        Value::String { val, .. } => {
+            let path = PathBuf::from(engine.get_current_dir()?).join(val);
+            std::fs::read(&path).map_err(|e| {
+                LabeledError::new(format!("Failed to read {}: {}", path.display(), e))
+                    .with_label("Failed to read the package", value.span())
+            })
+        }
+        _ => Err(LabeledError::new(format!(
+            "requires a path or a binary, got {}",
+            value.get_type()
+        ))
+        .with_label("Unsupported type", value.span())),
+    }
+}
+
+/**
+ * Get the sections of a package, a section that cannot be decoded is an error without failing the other sections
+ */
+fn get_package_sections(
+    bytes: &[u8],
+) -> Result<Vec<(&'static str, Result<Section, LabeledError>)>, LabeledError> {
+    let header =
+        AssetHeader::new(Cursor::new(bytes)).map_err(|e| LabeledError::new(e.to_string()))?;
+    let exports = uasset_reader::read_exports(bytes, &header);
+    let resolver = ObjectResolver {
+        header: &header,
+        exports: exports.as_deref().unwrap_or_default(),
+        package_name: None,
+    };
+
+    let version = &header.engine_version;
+    let engine_version = to_section([
+        (
+            String::new(),
+            "engine_version".to_string(),
+            format!(
+                "{}.{}.{}-{}+{}",
+                version.major,
+                version.minor,
+                version.patch,
+                version.changelist,
+                version.branch_name
+            ),
+        ),
+        (
+            String::new(),
+            "file_version".to_string(),
+            header.archive.file_version.to_string(),
+        ),
+        (
+            String::new(),
+            "file_version_ue5".to_string(),
+            header
+                .archive
+                .file_version_ue5
+                .map(|version| version.to_string())
+                .unwrap_or_default(),
+        ),
+    ]);
+    let names = to_section(
+        header
+            .names
+            .iter()
+            .map(|name| (String::new(), name.clone(), String::new())),
+    );
+    // Imports are compared by class and path
+    let imports = to_section((0..header.imports.len() as i32).filter_map(|index| {
+        Some((
+            resolver.class_path(-index - 1)?,
+            resolver.object_path(-index - 1)?,
+            String::new(),
+        ))
+    }));
+    // Exports are compared by path, their class and serial size tell if they changed
+    let export_entries = exports.as_ref().map_err(Clone::clone).map(|exports| {
+        to_section((1..=exports.len() as i32).filter_map(|index| {
+            let export = &exports[(index - 1) as usize];
+            Some((
+                String::new(),
+                resolver.object_path(index)?,
+                format!(
+                    "{} ({} bytes)",
+                    resolver.class_path(index).unwrap_or_default(),
+                    export.serial_size
+                ),
+            ))
+        }))
+    });
+    // A package can be both a hard and a soft dependency
+    let dependencies = uasset_reader::read_soft_package_dependencies(bytes, &header).map(|soft| {
+        to_section(
+            uasset_reader::read_package_imports(&header)
+                .into_iter()
+                .map(|package| ("hard".to_string(), package, String::new()))
+                .chain(
+                    soft.into_iter()
+                        .map(|package| ("soft".to_string(), package, String::new())),
+                ),
+        )
+    });
+
+    Ok(vec![
+        ("engine_version", Ok(engine_version)),
+        ("names", Ok(names)),
+        ("imports", Ok(imports)),
+        ("exports", export_entries),
+        ("dependencies", dependencies),
+    ])
+}
+
+fn diff_section(section: &str, old: &Section, new: &Section, span: Span) -> Vec<Value> {
+    let to_value = |value: Option<&String>| {
+        value
+            .map(|value| Value::string(value, span))
+            .unwrap_or_default()
+    };
+    let mut keys: Vec<&(String, String, usize)> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter_map(|key| {
+            let (old_value, new_value) = (old.get(key), new.get(key));
+            let change = match (old_value, new_value) {
+                (Some(_), None) => "removed",
+                (None, Some(_)) => "added",
+                (Some(old_value), Some(new_value)) if old_value != new_value => "changed",
+                _ => return None,
+            };
+            let (kind, name, _) = key;
+            Some(Value::record(
+                record! {
+                    "section" => Value::string(section, span),
+                    "kind" => if kind.is_empty() { Value::nothing(span) } else { Value::string(kind, span) },
+                    "key" => Value::string(name, span),
+                    "change" => Value::string(change, span),
+                    "old" => to_value(old_value),
+                    "new" => to_value(new_value),
+                },
+                span,
+            ))
+        })
+        .collect()
+}
+
+// A section that cannot be decoded in one of the packages is reported with its errors
+fn diff_section_results(
+    section: &str,
+    old: &Result<Section, LabeledError>,
+    new: &Result<Section, LabeledError>,
+    span: Span,
+) -> Vec<Value> {
+    match (old, new) {
+        (Ok(old), Ok(new)) => diff_section(section, old, new, span),
+        _ => {
+            let to_value = |result: &Result<Section, LabeledError>| {
+                result
+                    .as_ref()
+                    .err()
+                    .map(|e| Value::string(&e.msg, span))
+                    .unwrap_or_default()
+            };
+            vec![Value::record(
+                record! {
+                    "section" => Value::string(section, span),
+                    "kind" => Value::nothing(span),
+                    "key" => Value::nothing(span),
+                    "change" => Value::string("error", span),
+                    "old" => to_value(old),
+                    "new" => to_value(new),
+                },
+                span,
+            )]
+        }
+    }
+}
+
+impl SimplePluginCommand for UEAssetDiff {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "ue asset-diff"
+    }
+
+    fn description(&self) -> &str {
+        "Compare the names, imports, exports, engine version and dependencies of two packages {section, kind, key, change, old, new}, a section that cannot be decoded is an error change"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "old",
+                SyntaxShape::OneOf(vec![SyntaxShape::Filepath, SyntaxShape::Binary]),
+                "Path or content of the old package",
+            )
+            .required(
+                "new",
+                SyntaxShape::OneOf(vec![SyntaxShape::Filepath, SyntaxShape::Binary]),
+                "Path or content of the new package",
+            )
+            .category(Category::Plugin)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "ue asset-diff Content/Old/BP_Door.uasset Content/Blueprints/BP_Door.uasset",
+                description: "Compare two packages",
+                result: None,
+            },
+            Example {
+                example: "ue asset-diff (git show HEAD~1:Content/Blueprints/BP_Door.uasset | into binary) Content/Blueprints/BP_Door.uasset",
+                description: "Compare a package with its previous revision",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let old = get_package_sections(&get_package_bytes(engine, &call.req(0)?)?)?;
+        let new = get_package_sections(&get_package_bytes(engine, &call.req(1)?)?)?;
+
+        let mut changes = vec![];
+        for ((section, old_entries), (_, new_entries)) in old.iter().zip(new.iter()) {
+            changes.extend(diff_section_results(
+                section,
+                old_entries,
+                new_entries,
+                call.head,
+            ));
+        }
+        Ok(Value::list(changes, call.head))
+    }
+}
+
+#[cfg(test)]
+fn test_sections(bytes: &[u8]) -> Vec<(&'static str, Result<Section, LabeledError>)> {
+    get_package_sections(bytes).unwrap()
+}
+
+#[cfg(test)]
+fn test_changes(
+    old: &[(&'static str, Result<Section, LabeledError>)],
+    new: &[(&'static str, Result<Section, LabeledError>)],
+) -> Vec<(String, Option<String>, String, String)> {
+    let span = Span::test_data();
+    old.iter()
+        .zip(new.iter())
+        .flat_map(|((section, old), (_, new))| diff_section_results(section, old, new, span))
+        .map(|change| {
+            let get = |column: &str| change.get_data_by_key(column).unwrap();
+            (
+                get("section").as_str().unwrap().to_string(),
+                get("kind").as_str().ok().map(str::to_string),
+                get("key").as_str().unwrap_or_default().to_string(),
+                get("change").as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_diff_packages() {
+    let old = test_sections(include_bytes!(
+        "../../tests/fixtures/Content/Legacy/SM_Crate.uasset"
+    ));
+    let new = test_sections(include_bytes!(
+        "../../tests/fixtures/Content/Meshes/SM_Rock.uasset"
+    ));
+    let changes = test_changes(&old, &new);
+    let change = |section: &str, kind: Option<&str>, key: &str| {
+        changes
+            .iter()
+            .find(|(s, k, name, _)| s == section && k.as_deref() == kind && name == key)
+            .map(|(_, _, _, change)| change.as_str())
+    };
+
+    assert_eq!(
+        change("engine_version", None, "engine_version"),
+        Some("changed")
+    );
+    assert_eq!(
+        change("engine_version", None, "file_version_ue5"),
+        Some("changed")
+    );
+    assert_eq!(change("engine_version", None, "file_version"), None);
+    assert_eq!(change("names", None, "SM_Crate"), Some("removed"));
+    assert_eq!(change("names", None, "SM_Rock"), Some("added"));
+    assert_eq!(change("names", None, "BodySetup"), None);
+    assert_eq!(
+        change(
+            "imports",
+            Some("/Script/Engine.Material"),
+            "/Game/Materials/M_Rock.M_Rock"
+        ),
+        Some("added")
+    );
+    assert_eq!(change("exports", None, "SM_Crate"), Some("removed"));
+    assert_eq!(change("exports", None, "SM_Rock"), Some("added"));
+    assert_eq!(
+        change("dependencies", Some("hard"), "/Game/Materials/M_Rock"),
+        Some("added")
+    );
+    assert_eq!(
+        change("dependencies", Some("soft"), "/Game/Textures/T_Rock"),
+        Some("added")
+    );
+    assert_eq!(change("dependencies", Some("hard"), "/Script/Engine"), None);
+
+    // The serial size of an export tells that it changed
+    let mut new_exports = old[3].1.as_ref().unwrap().clone();
+    new_exports.insert(
+        (String::new(), "SM_Crate".to_string(), 0),
+        "/Script/Engine.StaticMesh (16 bytes)".to_string(),
+    );
+    let changes = diff_section(
+        "exports",
+        old[3].1.as_ref().unwrap(),
+        &new_exports,
+        Span::test_data(),
+    );
+    assert_eq!(changes.len(), 1);
+    assert_eq!(
+        changes[0]
+            .get_data_by_key("change")
+            .unwrap()
+            .as_str()
+            .unwrap(),
+        "changed"
+    );
+    assert_eq!(
+        changes[0].get_data_by_key("old").unwrap().as_str().unwrap(),
+        "/Script/Engine.StaticMesh (8 bytes)"
+    );
+}
+
+#[test]
+fn test_diff_duplicate_entries() {
+    let entry = |kind: &str, name: &str| (kind.to_string(), name.to_string(), String::new());
+    // The same package as a hard and a soft dependency, and the same import twice
+    let old = to_section([
+        entry("hard", "/Game/Materials/M_Rock"),
+        entry("soft", "/Game/Materials/M_Rock"),
+        entry("/Script/Engine.Material", "/Game/Materials/M_Rock.M_Rock"),
+        entry("/Script/Engine.Material", "/Game/Materials/M_Rock.M_Rock"),
+    ]);
+    assert_eq!(old.len(), 4);
+
+    let new = to_section([
+        entry("hard", "/Game/Materials/M_Rock"),
+        entry("/Script/Engine.Material", "/Game/Materials/M_Rock.M_Rock"),
+    ]);
+    let changes = test_changes(&[("entries", Ok(old))], &[("entries", Ok(new))]);
+    assert_eq!(
+        changes,
+        vec![
+            (
+                "entries".to_string(),
+                Some("/Script/Engine.Material".to_string()),
+                "/Game/Materials/M_Rock.M_Rock".to_string(),
+                "removed".to_string()
+            ),
+            (
+                "entries".to_string(),
+                Some("soft".to_string()),
+                "/Game/Materials/M_Rock".to_string(),
+                "removed".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_diff_undecodable_section() {
+    let bytes = include_bytes!("../../tests/fixtures/Content/Meshes/SM_Rock.uasset");
+    let header = AssetHeader::new(Cursor::new(bytes.as_slice())).unwrap();
+
+    // Claim more exports than the package contains
+    let mut invalid_bytes = bytes.to_vec();
+    let export_table = [
+        header.export_count.to_le_bytes(),
+        header.export_offset.to_le_bytes(),
+    ]
+    .concat();
+    let position = invalid_bytes
+        .windows(8)
+        .position(|w| w == export_table)
+        .unwrap();
+    invalid_bytes[position..position + 4].copy_from_slice(&1000i32.to_le_bytes());
+
+    // Only the exports are reported, the other sections are the same
+    let changes = test_changes(&test_sections(bytes), &test_sections(&invalid_bytes));
+    assert_eq!(
+        changes,
+        vec![(
+            "exports".to_string(),
+            None,
+            String::new(),
+            "error".to_string()
+        )]
+    );
+}
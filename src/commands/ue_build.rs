@@ -1,17 +1,98 @@
 use std::{path::PathBuf, process::Command};
 
 use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
-use nu_protocol::{Category, Example, LabeledError, PipelineData, Signature, SyntaxShape};
+use nu_protocol::{
+    Category, Example, LabeledError, PipelineData, Signature, Spanned, SyntaxShape, did_you_mean,
+};
 
 use crate::{
     UnrealEnginePlugin,
-    utils::{ue_paths, ue_tools, uproject},
+    utils::{choices::validate_choice, rules, ue_paths, ue_tools, uproject},
 };
 
 pub struct UEBuild;
 
 // https://dev.epicgames.com/documentation/en-us/unreal-engine/unreal-build-tool-in-unreal-engine
 
+// UnrealTargetPlatform
+const UBT_PLATFORMS: &[&str] = &[
+    "Win64",
+    "Mac",
+    "IOS",
+    "Android",
+    "Linux",
+    "LinuxArm64",
+    "TVOS",
+    "VisionOS",
+];
+
+// UnrealTargetConfiguration
+const UBT_CONFIGURATIONS: &[&str] = &["Debug", "DebugGame", "Development", "Shipping", "Test"];
+
+#[cfg(target_os = "windows")]
+const HOST_PLATFORM: &str = "Win64";
+#[cfg(target_os = "macos")]
+const HOST_PLATFORM: &str = "Mac";
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+const HOST_PLATFORM: &str = "LinuxArm64";
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    all(target_os = "linux", target_arch = "aarch64")
+)))]
+const HOST_PLATFORM: &str = "Linux";
+
+// Targets of the engine accepted without walking the engine Source directory, which is slow on source builds
+const ENGINE_TARGETS: &[&str] = &[
+    "UnrealEditor",
+    "UnrealGame",
+    "UnrealClient",
+    "UnrealServer",
+    "ShaderCompileWorker",
+    "UnrealPak",
+    "UnrealInsights",
+    "UnrealLightmass",
+    "CrashReportClient",
+    "LiveCodingConsole",
+];
+
+// Get the target or the default <ProjectName>Editor, and check it is one of the targets found
+fn validate_target(
+    target: Option<Spanned<String>>,
+    uproject: &uproject::UProject,
+    unreal_engine_path: &PathBuf,
+) -> Result<String, LabeledError> {
+    let project_dir = uproject.uproject_path.parent().unwrap().to_path_buf();
+    let project_targets = rules::find_targets(&project_dir, None)?;
+    let target_name = match &target {
+        Some(target) => target.item.clone(),
+        // Blueprint-only projects are built with the engine editor target
+        None if project_targets.is_empty() => "UnrealEditor".to_string(),
+        None => uproject.name.clone() + "Editor",
+    };
+    if project_targets.iter().any(|t| t.name == target_name)
+        || ENGINE_TARGETS.contains(&target_name.as_str())
+    {
+        return Ok(target_name);
+    }
+
+    // Engine targets are only parsed when needed, source builds contain a lot of programs
+    let targets = rules::find_targets(&project_dir, Some(unreal_engine_path))?;
+    if targets.iter().any(|t| t.name == target_name) {
+        return Ok(target_name);
+    }
+
+    let names: Vec<&str> = targets.iter().map(|t| t.name.as_str()).collect();
+    let mut error = LabeledError::new(format!("Unknown target '{}'", target_name));
+    if let Some(target) = target {
+        error = error.with_label("unknown target", target.span);
+    }
+    Err(match did_you_mean(&names, &target_name) {
+        Some(suggestion) => error.with_help(format!("Did you mean '{}'?", suggestion)),
+        None => error.with_help("List the available targets with `ue targets`"),
+    })
+}
+
 impl PluginCommand for UEBuild {
     type Plugin = UnrealEnginePlugin;
 
@@ -25,22 +106,78 @@ impl PluginCommand for UEBuild {
 
     fn signature(&self) -> Signature {
         Signature::build(self.name())
+            .optional(
+                "target",
+                SyntaxShape::String,
+                "Target to build (see `ue targets`), default is <ProjectName>Editor",
+            )
+            .optional(
+                "platform",
+                SyntaxShape::String,
+                "Platform to build for, default is the host platform",
+            )
+            .optional(
+                "configuration",
+                SyntaxShape::String,
+                "Configuration to build (Debug, DebugGame, Development, Shipping, Test), default is Development",
+            )
+            .rest(
+                "args",
+                SyntaxShape::String,
+                "Additional arguments for UnrealBuildTool",
+            )
             .named(
                 "uproject",
                 SyntaxShape::Filepath,
                 "Path to a uproject, default is the .uproject file of the current directory",
                 Some('u'),
             )
+            .switch("waitmutex", "Wait for another instance of UBT to finish (-WaitMutex)", None)
+            .switch("no-hot-reload", "Disable hot reload (-NoHotReload)", None)
+            .switch("clean", "Clean the intermediate files of the target (-Clean)", None)
+            .switch("rebuild", "Clean and build the target (-Rebuild)", None)
+            .switch(
+                "diagnostics",
+                "Stream the errors and warnings as records {file, line, column, severity, code, message, module}",
+                Some('d'),
+            )
+            .switch(
+                "stream",
+                "Stream each output line as a record {line, stream, timestamp}",
+                None,
+            )
+            .switch(
+                "no-fail",
+                "Return a record {exit_code, stdout, stderr, duration} instead of failing on a non-zero exit code",
+                None,
+            )
             .allows_unknown_args()
             .category(Category::Plugin)
     }
 
     fn examples(&self) -> Vec<Example> {
-        vec![Example {
-            example: "ue build VestigeEditor Win64 Development",
-            description: "Build the project with editor features",
-            result: None,
-        }]
+        vec![
+            Example {
+                example: "ue build VestigeEditor Win64 Development",
+                description: "Build the project with editor features",
+                result: None,
+            },
+            Example {
+                example: "ue build --rebuild Vestige Win64 Shipping",
+                description: "Rebuild the game in Shipping",
+                result: None,
+            },
+            Example {
+                example: "ue build --diagnostics VestigeEditor Win64 Development | where severity == error",
+                description: "Build the project and list the errors",
+                result: None,
+            },
+            Example {
+                example: "ue build --stream VestigeEditor Win64 Development | save build.nuon",
+                description: "Build the project and save the output lines",
+                result: None,
+            },
+        ]
     }
 
     fn run(
@@ -50,21 +187,65 @@ impl PluginCommand for UEBuild {
         call: &EvaluatedCall,
         _input: PipelineData,
     ) -> Result<PipelineData, LabeledError> {
+        // Both switches set how the output is returned
+        if call.has_flag("diagnostics")? && call.has_flag("stream")? {
+            return Err(
+                LabeledError::new("--diagnostics and --stream cannot be used together")
+                    .with_label("Use either --diagnostics or --stream", call.head),
+            );
+        }
+
         let uproject_path: PathBuf =
             uproject::uproject_from_arg_or_current_dir(&engine, call.get_flag("uproject")?)?;
 
-        let args: Vec<String> = call.rest(0).map_err(|e| LabeledError::new(e.to_string()))?;
+        let target: Option<Spanned<String>> = call.opt(0)?;
+        // The platform and configuration are passed with the case expected by UnrealBuildTool
+        let platform = call
+            .opt(1)?
+            .map(|platform| validate_choice(platform, UBT_PLATFORMS, "platform"))
+            .transpose()?;
+        let configuration = call
+            .opt(2)?
+            .map(|configuration| {
+                validate_choice(configuration, UBT_CONFIGURATIONS, "configuration")
+            })
+            .transpose()?;
+        let args: Vec<String> = call.rest(3).map_err(|e| LabeledError::new(e.to_string()))?;
 
         let uproject = uproject::UProject::from_path(&uproject_path)?;
-        let unreal_build_path = ue_paths::get_ubt_path(uproject.get_unreal_engine_path_str()?);
+        let unreal_engine_path = uproject.get_unreal_engine_path_str()?;
+        let unreal_build_path = ue_paths::get_ubt_path(unreal_engine_path)?;
         let mut command = Command::new(&unreal_build_path);
 
+        let target = validate_target(target, &uproject, unreal_engine_path)?;
+
         command
             .current_dir(&engine.get_current_dir()?)
-            .args(["-uproject=", uproject_path.to_str().unwrap()])
-            .args(&args);
+            .arg(target)
+            .arg(platform.unwrap_or(HOST_PLATFORM.to_string()))
+            .arg(configuration.unwrap_or("Development".to_string()))
+            .args(["-uproject=", uproject_path.to_str().unwrap()]);
+
+        if call.has_flag("waitmutex")? {
+            command.arg("-WaitMutex");
+        }
+        if call.has_flag("no-hot-reload")? {
+            command.arg("-NoHotReload");
+        }
+        if call.has_flag("clean")? {
+            command.arg("-Clean");
+        }
+        if call.has_flag("rebuild")? {
+            command.arg("-Rebuild");
+        }
+        command.args(&args);
 
         // Execute the command and return the output
-        ue_tools::run(&mut command, call.head)
+        if call.has_flag("diagnostics")? {
+            let fail = !call.has_flag("no-fail")?;
+            ue_tools::run_diagnostics(&mut command, call.head, engine.signals(), fail)
+        } else {
+            ue_tools::run_from_call(&mut command, engine, call)
+        }
     }
 }
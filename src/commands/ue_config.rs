@@ -0,0 +1,237 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Spanned, SyntaxShape, Value};
+
+use crate::{
+    UnrealEnginePlugin,
+    utils::{
+        choices::validate_choice,
+        ue_config,
+        ue_ini::{self, IniEdit, IniValue},
+        uproject,
+    },
+};
+
+pub struct UEConfig;
+pub struct UEConfigSet;
+
+// Config files with a Base*.ini in Engine/Config
+const CONFIG_TYPES: &[&str] = &[
+    "Engine",
+    "Game",
+    "Input",
+    "Editor",
+    "EditorPerProjectUserSettings",
+    "EditorSettings",
+    "GameUserSettings",
+    "Scalability",
+    "DeviceProfiles",
+    "Hardware",
+    "Lightmass",
+];
+
+impl SimplePluginCommand for UEConfig {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "ue config"
+    }
+
+    fn description(&self) -> &str {
+        "Merge the config hierarchy of the engine and the project {section, key, value, file}, with the file that set each value"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "type",
+                SyntaxShape::String,
+                "Config type: Engine, Game, Input, Editor...",
+            )
+            .named(
+                "platform",
+                SyntaxShape::String,
+                "Platform of the config files, default is the current platform",
+                Some('p'),
+            )
+            .switch(
+                "game",
+                "Read the config saved by the game (Saved/Config/<Platform>) instead of the editor (Saved/Config/<Platform>Editor)",
+                Some('g'),
+            )
+            .named(
+                "uproject",
+                SyntaxShape::Filepath,
+                "Path to a uproject, default is the .uproject file of the current directory",
+                Some('u'),
+            )
+            .category(Category::Plugin)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "ue config Engine",
+                description: "Get the engine config of the project",
+                result: None,
+            },
+            Example {
+                example: "ue config Game --platform Linux | where key == MapsToCook",
+                description: "Get the maps to cook on Linux and the files that added them",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let config_type = validate_choice(call.req(0)?, CONFIG_TYPES, "config type")?;
+        let platform = match call.get_flag::<Spanned<String>>("platform")? {
+            Some(platform) => validate_choice(platform, ue_config::CONFIG_PLATFORMS, "platform")?,
+            None => ue_config::HOST_CONFIG_PLATFORM.to_string(),
+        };
+        let uproject_path =
+            uproject::uproject_from_arg_or_current_dir(&engine, call.get_flag("uproject")?)?;
+        let uproject = uproject::UProject::from_path(&uproject_path)?;
+
+        let hierarchy = ue_config::get_config_hierarchy(
+            uproject.get_unreal_engine_path_str()?,
+            &uproject_path.parent().unwrap().to_path_buf(),
+            &config_type,
+            &platform,
+            !call.has_flag("game")?,
+        );
+        let sections = ue_config::merge_config_files(&hierarchy)?;
+        Ok(Value::list(
+            sections
+                .iter()
+                .flat_map(|section| section.to_values(call.head))
+                .collect(),
+            call.head,
+        ))
+    }
+}
+
+impl SimplePluginCommand for UEConfigSet {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "ue config set"
+    }
+
+    fn description(&self) -> &str {
+        "Set, append or remove a key of Config/Default<Type>.ini, keeping the comments and the other lines as they are"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "type",
+                SyntaxShape::String,
+                "Config type: Engine, Game, Input, Editor...",
+            )
+            .required(
+                "section",
+                SyntaxShape::String,
+                "Section of the key, e.g. /Script/EngineSettings.GeneralProjectSettings",
+            )
+            .required("key", SyntaxShape::String, "Key to edit")
+            .optional(
+                "value",
+                SyntaxShape::Any,
+                "Value of the key, records are written as struct literals (A=1,B=\"x\")",
+            )
+            .switch("append", "Add the value with +Key=Value", Some('a'))
+            .switch(
+                "remove",
+                "Remove the value, or all the values of the key without value",
+                Some('r'),
+            )
+            .named(
+                "uproject",
+                SyntaxShape::Filepath,
+                "Path to a uproject, default is the .uproject file of the current directory",
+                Some('u'),
+            )
+            .category(Category::Plugin)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "ue config set Game /Script/EngineSettings.GeneralProjectSettings ProjectVersion 1.2.0",
+                description: "Set the version of the project",
+                result: None,
+            },
+            Example {
+                example: "ue config set Engine /Script/Engine.RendererSettings ConsoleVariables r.Shadow.Virtual.Enable=1 --append",
+                description: "Add a console variable",
+                result: None,
+            },
+            Example {
+                example: "ue config set Game /Script/UnrealEd.ProjectPackagingSettings MapsToCook {FilePath: /Game/Maps/Test} --remove",
+                description: "Remove a map to cook",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let config_type = validate_choice(call.req(0)?, CONFIG_TYPES, "config type")?;
+        let section: String = call.req(1)?;
+        let key: String = call.req(2)?;
+        let value = call
+            .opt::<Value>(3)?
+            .map(|value| IniValue::from_value(&value))
+            .transpose()?;
+        let edit = match (call.has_flag("append")?, call.has_flag("remove")?, value) {
+            (true, true, _) => {
+                return Err(
+                    LabeledError::new("--append and --remove cannot be used together")
+                        .with_label("Use either --append or --remove", call.head),
+                );
+            }
+            (false, true, value) => IniEdit::Remove(value),
+            (true, false, Some(value)) => IniEdit::Append(value),
+            (false, false, Some(value)) => IniEdit::Set(value),
+            (_, false, None) => {
+                return Err(LabeledError::new(format!("Missing value for {}", key))
+                    .with_label("Missing value", call.head)
+                    .with_help("A value is required to set or append a key"));
+            }
+        };
+
+        let uproject_path =
+            uproject::uproject_from_arg_or_current_dir(&engine, call.get_flag("uproject")?)?;
+        let ini_path = uproject_path
+            .parent()
+            .unwrap()
+            .join("Config")
+            .join(format!("Default{}.ini", config_type));
+        let content = if ini_path.exists() {
+            std::fs::read_to_string(&ini_path).map_err(|e| {
+                LabeledError::new(format!("Failed to read {}: {}", ini_path.display(), e))
+            })?
+        } else {
+            String::new()
+        };
+        // Nothing is written when there is nothing to change, e.g. removing a key of a missing file
+        let edited = ue_ini::edit_ini(&content, &section, &key, edit);
+        if edited != content {
+            std::fs::write(&ini_path, edited).map_err(|e| {
+                LabeledError::new(format!("Failed to write {}: {}", ini_path.display(), e))
+            })?;
+        }
+        Ok(Value::nothing(call.head))
+    }
+}
@@ -0,0 +1,96 @@
+use std::path::PathBuf;
+
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Value, record};
+
+use crate::{UnrealEnginePlugin, utils::ue_engines};
+
+pub struct UEEngines;
+
+// Get the search paths from $env.config.plugins.unreal_engine.engine_search_paths
+fn get_configured_search_paths(engine: &EngineInterface) -> Result<Vec<PathBuf>, LabeledError> {
+    let Some(config) = engine.get_plugin_config()? else {
+        return Ok(vec![]);
+    };
+    let Some(search_paths) = config.get_data_by_key("engine_search_paths") else {
+        return Ok(vec![]);
+    };
+    search_paths
+        .as_list()?
+        .iter()
+        .map(|path| -> Result<PathBuf, LabeledError> { Ok(PathBuf::from(path.as_str()?)) })
+        .collect()
+}
+
+impl SimplePluginCommand for UEEngines {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "ue engines"
+    }
+
+    fn description(&self) -> &str {
+        "List the Unreal Engine installations installed with the Launcher, registered or found in $env.config.plugins.unreal_engine.engine_search_paths"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name()).category(Category::Plugin)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "ue engines",
+                description: "List the Unreal Engine installations",
+                result: None,
+            },
+            Example {
+                example: "ue engines | where version =~ '^5.5'",
+                description: "Find the installations of Unreal Engine 5.5",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let search_paths = get_configured_search_paths(engine)?;
+
+        let mut installations = ue_engines::get_launcher_installations()?;
+        installations.extend(ue_engines::get_registered_installations()?);
+        for installation in ue_engines::get_search_path_installations(&search_paths) {
+            // The Launcher install directory is a common search path
+            if !installations.iter().any(|i| i.path == installation.path) {
+                installations.push(installation);
+            }
+        }
+
+        let mut engines = vec![];
+        for installation in installations {
+            // An engine whose Build.version cannot be read is listed without version
+            let build_version =
+                ue_engines::get_build_version(&installation.path).unwrap_or_else(|e| {
+                    eprintln!("Warning: {}", e.msg);
+                    None
+                });
+            engines.push(Value::record(
+                record! {
+                    "identifier" => Value::string(installation.identifier, call.head),
+                    "path" => Value::string(installation.path.display().to_string(), call.head),
+                    "version" => build_version.as_ref().map(|v| Value::string(v.to_string(), call.head)).unwrap_or_default(),
+                    "changelist" => build_version.as_ref().map(|v| Value::int(v.changelist, call.head)).unwrap_or_default(),
+                    "branch" => build_version.as_ref().map(|v| Value::string(&v.branch_name, call.head)).unwrap_or_default(),
+                    "installed_build" => Value::bool(ue_engines::is_installed_build(&installation.path), call.head),
+                    "source" => Value::string(installation.source.to_string(), call.head),
+                },
+                call.head,
+            ));
+        }
+        Ok(Value::list(engines, call.head))
+    }
+}
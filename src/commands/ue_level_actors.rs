@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, SyntaxShape, Value};
+
+use crate::{
+    UnrealEnginePlugin,
+    utils::{asset_scan, level, ue_paths, uproject},
+};
+
+pub struct UELevelActors;
+
+impl SimplePluginCommand for UELevelActors {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "ue level-actors"
+    }
+
+    fn description(&self) -> &str {
+        "List the external actors of a World Partition map {file, package, name, class, data_layers} from Content/__ExternalActors__"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "map",
+                SyntaxShape::String,
+                "Path to a .umap file or package name of the map, e.g. /Game/Maps/Main",
+            )
+            .named(
+                "uproject",
+                SyntaxShape::Filepath,
+                "Path to a uproject, default is the .uproject file of the current directory",
+                Some('u'),
+            )
+            .category(Category::Plugin)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "ue level-actors Content/Maps/Main.umap",
+                description: "List the external actors of a map",
+                result: None,
+            },
+            Example {
+                example: "ue level-actors /Game/Maps/Main | where data_layers has /Game/DataLayers/DL_Night.DL_Night | get file",
+                description: "Get the files of the actors in a data layer",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let map: String = call.req(0)?;
+        let map_path = if let Some(relative_path) = map.strip_prefix("/Game/") {
+            let uproject_path =
+                uproject::uproject_from_arg_or_current_dir(&engine, call.get_flag("uproject")?)?;
+            uproject_path
+                .parent()
+                .unwrap()
+                .join("Content")
+                .join(format!("{}.umap", relative_path))
+        } else {
+            PathBuf::from(engine.get_current_dir()?).join(&map)
+        };
+        if !map_path.exists() {
+            return Err(
+                LabeledError::new(format!("Map not found: {}", map_path.display()))
+                    .with_label("Map not found", call.head)
+                    .with_help("Use the path of a .umap file or a /Game package name"),
+            );
+        }
+
+        let directory = ue_paths::get_external_actors_directory(&map_path).ok_or_else(|| {
+            LabeledError::new(format!(
+                "{} is not in a Content directory",
+                map_path.display()
+            ))
+            .with_label("Not in a Content directory", call.head)
+        })?;
+        let mut actors = vec![];
+        for path in ue_paths::find_files(&directory, &[".uasset"]) {
+            match level::read_external_actor(&path) {
+                Ok(actor) => actors.push(actor.to_value(&directory, call.head)),
+                Err(e) => asset_scan::warn_skipped_package(&e),
+            }
+        }
+        Ok(Value::list(actors, call.head))
+    }
+}
@@ -0,0 +1,87 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, SyntaxShape, Value, record};
+
+use crate::{
+    UnrealEnginePlugin,
+    utils::{rules, uproject},
+};
+
+pub struct UEModules;
+
+impl SimplePluginCommand for UEModules {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "ue modules"
+    }
+
+    fn description(&self) -> &str {
+        "List the modules of the project and its plugins from the *.Build.cs files"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .named(
+                "uproject",
+                SyntaxShape::Filepath,
+                "Path to a uproject, default is the .uproject file of the current directory",
+                Some('u'),
+            )
+            .switch(
+                "graph",
+                "Return the dependency edges {from, to, kind, is_project_module}",
+                Some('g'),
+            )
+            .category(Category::Plugin)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "ue modules",
+                description: "List the modules of the project",
+                result: None,
+            },
+            Example {
+                example: "ue modules --graph | where is_project_module and kind == public",
+                description: "List the public dependencies between modules of the project",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let uproject_path =
+            uproject::uproject_from_arg_or_current_dir(&engine, call.get_flag("uproject")?)?;
+        let modules = rules::find_modules(&uproject_path.parent().unwrap().to_path_buf())?;
+
+        if !call.has_flag("graph")? {
+            return Ok(Value::list(
+                modules.iter().map(|m| m.to_value(call.head)).collect(),
+                call.head,
+            ));
+        }
+
+        let mut edges = vec![];
+        for module in &modules {
+            for (dependency, kind) in module.dependencies() {
+                edges.push(Value::record(
+                    record! {
+                        "from" => Value::string(&module.name, call.head),
+                        "to" => Value::string(dependency, call.head),
+                        "kind" => Value::string(kind, call.head),
+                        "is_project_module" => Value::bool(modules.iter().any(|m| m.name == dependency), call.head),
+                    },
+                    call.head,
+                ));
+            }
+        }
+        Ok(Value::list(edges, call.head))
+    }
+}
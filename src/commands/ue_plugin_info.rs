@@ -0,0 +1,86 @@
+use std::path::PathBuf;
+
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, LabeledError, Signature, Spanned, SyntaxShape, Value, record,
+};
+
+use crate::{UnrealEnginePlugin, utils::uplugin::PluginDescriptor};
+
+pub struct UEPluginInfo;
+
+impl SimplePluginCommand for UEPluginInfo {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "ue plugin-info"
+    }
+
+    fn description(&self) -> &str {
+        "Get the descriptor of a plugin with its modules and plugin dependencies"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "path",
+                SyntaxShape::Filepath,
+                "Path to a .uplugin file or to the directory of a plugin",
+            )
+            .category(Category::Plugin)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "ue plugin-info Plugins/MyPlugin",
+                description: "Get the descriptor of a project plugin",
+                result: None,
+            },
+            Example {
+                example: "ue plugins | where source == Marketplace | each { ue plugin-info $in.path } | select name engine_version",
+                description: "Get the engine versions of the Marketplace plugins",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let path: Spanned<String> = call.req(0)?;
+        let mut uplugin_path = PathBuf::from(engine.get_current_dir()?).join(&path.item);
+        if uplugin_path.is_dir() {
+            // The .uplugin file is at the root of the plugin directory
+            uplugin_path = uplugin_path
+                .read_dir()
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .find(|path| path.extension().is_some_and(|ext| ext == "uplugin"))
+                .ok_or(
+                    LabeledError::new(format!(
+                        "Failed to find a .uplugin file in {}",
+                        uplugin_path.display()
+                    ))
+                    .with_label("Not a plugin directory", path.span),
+                )?;
+        }
+        let descriptor = PluginDescriptor::from_path(&uplugin_path)
+            .map_err(|e| e.with_label("Failed to read the plugin", path.span))?;
+
+        let mut record = record! {
+            "name" => Value::string(uplugin_path.file_stem().unwrap().to_string_lossy(), call.head),
+            "path" => Value::string(uplugin_path.display().to_string(), call.head),
+        };
+        if let Value::Record { val, .. } = descriptor.to_value(call.head) {
+            record.extend(val.into_owned());
+        }
+        Ok(Value::record(record, call.head))
+    }
+}
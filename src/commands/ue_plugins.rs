@@ -0,0 +1,215 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, LabeledError, Signature, Spanned, SyntaxShape, Value, did_you_mean, record,
+};
+
+use crate::{
+    UnrealEnginePlugin,
+    utils::{uplugin, uproject},
+};
+
+pub struct UEPlugins;
+pub struct UEPluginsEnable;
+pub struct UEPluginsDisable;
+
+fn uproject_signature(signature: Signature) -> Signature {
+    signature
+        .named(
+            "uproject",
+            SyntaxShape::Filepath,
+            "Path to a uproject, default is the .uproject file of the current directory",
+            Some('u'),
+        )
+        .category(Category::Plugin)
+}
+
+// Enable or disable a plugin found in the engine or the project
+fn set_plugin_enabled(
+    engine: &EngineInterface,
+    call: &EvaluatedCall,
+    enabled: bool,
+) -> Result<Value, LabeledError> {
+    let name: Spanned<String> = call.req(0)?;
+    let uproject_path =
+        uproject::uproject_from_arg_or_current_dir(&engine, call.get_flag("uproject")?)?;
+    let uproject = uproject::UProject::from_path(&uproject_path)?;
+
+    let plugins = uplugin::find_plugins(
+        uproject.unreal_engine_path.as_ref(),
+        &uproject_path.parent().unwrap().to_path_buf(),
+    );
+    let Some(plugin) = plugins
+        .iter()
+        .find(|plugin| plugin.name.eq_ignore_ascii_case(&name.item))
+    else {
+        let names: Vec<&str> = plugins.iter().map(|plugin| plugin.name.as_str()).collect();
+        let error = LabeledError::new(format!("Unknown plugin '{}'", name.item))
+            .with_label("unknown plugin", name.span);
+        return Err(match did_you_mean(&names, &name.item) {
+            Some(suggestion) => error.with_help(format!("Did you mean '{}'?", suggestion)),
+            None => error.with_help("Use `ue plugins` to list the plugins of the project"),
+        });
+    };
+
+    uproject::set_plugin_enabled(
+        &uproject_path,
+        &plugin.name,
+        enabled,
+        plugin.descriptor.marketplace_url.as_deref(),
+    )?;
+    Ok(Value::nothing(call.head))
+}
+
+impl SimplePluginCommand for UEPlugins {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "ue plugins"
+    }
+
+    fn description(&self) -> &str {
+        "List the plugins of the engine and the project from the .uplugin files, and whether the .uproject enables them"
+    }
+
+    fn signature(&self) -> Signature {
+        uproject_signature(Signature::build(self.name()))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "ue plugins",
+                description: "List the plugins",
+                result: None,
+            },
+            Example {
+                example: "ue plugins | where enabled and source != Engine",
+                description: "List the enabled plugins of the project and the Marketplace",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let uproject_path =
+            uproject::uproject_from_arg_or_current_dir(&engine, call.get_flag("uproject")?)?;
+        let uproject = uproject::UProject::from_path(&uproject_path)?;
+        // The Plugins of the .uproject set which plugins are enabled
+        let descriptor = uproject.get_descriptor()?;
+
+        let plugins = uplugin::find_plugins(
+            uproject.unreal_engine_path.as_ref(),
+            &uproject_path.parent().unwrap().to_path_buf(),
+        );
+        let span = call.head;
+        Ok(Value::list(
+            plugins
+                .iter()
+                .map(|plugin| {
+                    let reference = descriptor
+                        .plugins
+                        .iter()
+                        .find(|reference| reference.name.eq_ignore_ascii_case(&plugin.name));
+                    let enabled_by_default = plugin.is_enabled_by_default();
+                    Value::record(
+                        record! {
+                            "name" => Value::string(&plugin.name, span),
+                            "friendly_name" => Value::string(&plugin.descriptor.friendly_name, span),
+                            "version" => Value::string(&plugin.descriptor.version_name, span),
+                            "category" => Value::string(&plugin.descriptor.category, span),
+                            "source" => Value::string(plugin.source.to_string(), span),
+                            "enabled_by_default" => Value::bool(enabled_by_default, span),
+                            "enabled_in_uproject" => reference.map(|r| Value::bool(r.enabled, span)).unwrap_or_default(),
+                            "enabled" => Value::bool(reference.map_or(enabled_by_default, |r| r.enabled), span),
+                            "path" => Value::string(plugin.uplugin_path.display().to_string(), span),
+                        },
+                        span,
+                    )
+                })
+                .collect(),
+            span,
+        ))
+    }
+}
+
+impl SimplePluginCommand for UEPluginsEnable {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "ue plugins enable"
+    }
+
+    fn description(&self) -> &str {
+        "Enable a plugin in the Plugins of the .uproject"
+    }
+
+    fn signature(&self) -> Signature {
+        uproject_signature(Signature::build(self.name()).required(
+            "name",
+            SyntaxShape::String,
+            "Name of the plugin, the name of its .uplugin file",
+        ))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            example: "ue plugins enable PythonScriptPlugin",
+            description: "Enable the Python Editor Script Plugin",
+            result: None,
+        }]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        set_plugin_enabled(engine, call, true)
+    }
+}
+
+impl SimplePluginCommand for UEPluginsDisable {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "ue plugins disable"
+    }
+
+    fn description(&self) -> &str {
+        "Disable a plugin in the Plugins of the .uproject"
+    }
+
+    fn signature(&self) -> Signature {
+        uproject_signature(Signature::build(self.name()).required(
+            "name",
+            SyntaxShape::String,
+            "Name of the plugin, the name of its .uplugin file",
+        ))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            example: "ue plugins disable ModelingToolsEditorMode",
+            description: "Disable the Modeling Tools Editor Mode plugin",
+            result: None,
+        }]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        set_plugin_enabled(engine, call, false)
+    }
+}
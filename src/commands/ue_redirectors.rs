@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Span, SyntaxShape, Value, record};
+
+use crate::{
+    UnrealEnginePlugin,
+    utils::{
+        asset_scan::{self, PackageDependencies, PackageFile},
+        uasset_reader::{self, ObjectResolver},
+        uproject,
+    },
+};
+
+pub struct UERedirectors;
+
+/**
+ * Get the destination of a package with a top level ObjectRedirector export, None for other packages.
+ * The destination is null when it cannot be resolved.
+ */
+fn get_redirector_target(package: &PackageFile, span: Span) -> Result<Option<Value>, LabeledError> {
+    let header = package.header()?;
+    let exports = uasset_reader::read_exports(&package.header_bytes, &header).map_err(|e| {
+        LabeledError::new(format!(
+            "Failed to read {}: {}",
+            package.path.display(),
+            e.msg
+        ))
+    })?;
+    let resolver = ObjectResolver {
+        header: &header,
+        exports: &exports,
+        package_name: Some(&package.package_name),
+    };
+    // Redirectors can be saved with other exports, e.g. a MetaData
+    let Some(redirector) = exports.iter().enumerate().find_map(|(index, export)| {
+        (export.outer_index == 0
+            && resolver.class_path(index as i32 + 1).as_deref()
+                == Some("/Script/CoreUObject.ObjectRedirector"))
+        .then_some(export)
+    }) else {
+        return Ok(None);
+    };
+
+    // UObjectRedirector::Serialize writes the DestinationObject after the properties of UObject,
+    // it is the package index in the last 4 bytes of the export data
+    let data = package.read_export_data(redirector)?;
+    let target = data
+        .len()
+        .checked_sub(4)
+        .map(|offset| i32::from_le_bytes(data[offset..].try_into().unwrap()))
+        .and_then(|index| resolver.object_path(index));
+    Ok(Some(
+        target
+            .map(|target| Value::string(target, span))
+            .unwrap_or_default(),
+    ))
+}
+
+// Read the dependencies of a package and its redirector target
+fn read_package(
+    package: &PackageFile,
+    span: Span,
+) -> Result<(PackageDependencies, Option<Value>), LabeledError> {
+    Ok((
+        package.dependencies()?,
+        get_redirector_target(package, span)?,
+    ))
+}
+
+impl SimplePluginCommand for UERedirectors {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "ue redirectors"
+    }
+
+    fn description(&self) -> &str {
+        "List the object redirectors of the project and plugins Content with their target and the packages still referencing them"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .named(
+                "uproject",
+                SyntaxShape::Filepath,
+                "Path to a uproject, default is the .uproject file of the current directory",
+                Some('u'),
+            )
+            .category(Category::Plugin)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "ue redirectors",
+                description: "List the redirectors of the project",
+                result: None,
+            },
+            Example {
+                example: "if (ue redirectors | where ($it.referencers | is-not-empty) | is-not-empty) { exit 1 }",
+                description: "Fail when redirectors are still referenced",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let uproject_path =
+            uproject::uproject_from_arg_or_current_dir(&engine, call.get_flag("uproject")?)?;
+
+        let mut redirectors = vec![];
+        let mut referencers: HashMap<String, Vec<String>> = HashMap::new();
+        for package in
+            asset_scan::open_package_files(&uproject_path.parent().unwrap().to_path_buf())
+        {
+            // Packages that cannot be read are skipped with a warning
+            let (package_dependencies, target) = match read_package(&package, call.head) {
+                Ok(result) => result,
+                Err(e) => {
+                    asset_scan::warn_skipped_package(&e);
+                    continue;
+                }
+            };
+
+            let mut dependencies = package_dependencies.hard;
+            dependencies.extend(package_dependencies.soft);
+            dependencies.sort();
+            dependencies.dedup();
+            for dependency in dependencies {
+                referencers
+                    .entry(dependency)
+                    .or_default()
+                    .push(package.package_name.clone());
+            }
+
+            if let Some(target) = target {
+                redirectors.push((package.package_name.clone(), package.path.clone(), target));
+            }
+        }
+
+        let redirectors = redirectors
+            .into_iter()
+            .map(|(package_name, path, target)| {
+                let package_referencers = referencers
+                    .get(&package_name)
+                    .map(|packages| {
+                        packages
+                            .iter()
+                            .map(|referencer| Value::string(referencer, call.head))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Value::record(
+                    record! {
+                        "package" => Value::string(package_name, call.head),
+                        "path" => Value::string(path.display().to_string(), call.head),
+                        "target" => target,
+                        "referencers" => Value::list(package_referencers, call.head),
+                    },
+                    call.head,
+                )
+            })
+            .collect();
+        Ok(Value::list(redirectors, call.head))
+    }
+}
+
+#[test]
+fn test_get_redirector_target() {
+    let content =
+        std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/Content");
+    let span = Span::test_data();
+
+    let redirector = PackageFile::open(&content.join("Legacy/SM_Boulder.uasset")).unwrap();
+    assert_eq!(
+        get_redirector_target(&redirector, span).unwrap(),
+        Some(Value::string("/Game/Meshes/SM_Rock.SM_Rock", span))
+    );
+
+    // The other packages are not redirectors
+    let mesh = PackageFile::open(&content.join("Meshes/SM_Rock.uasset")).unwrap();
+    assert_eq!(get_redirector_target(&mesh, span).unwrap(), None);
+}
@@ -59,6 +59,11 @@ impl PluginCommand for UEStart {
                 "Start a server with -nosteam flag (Server only)",
                 None,
             )
+            .switch(
+                "no-fail",
+                "Do not end the stream with an error on a non-zero exit code",
+                None,
+            )
             .allows_unknown_args()
             .category(Category::Plugin)
     }
@@ -75,6 +80,11 @@ impl PluginCommand for UEStart {
                 description: "Start a client game for the project",
                 result: None,
             },
+            Example {
+                example: "ue start --editor --stdout | where stream == \"stderr\"",
+                description: "Start the editor and show its error output as it is written",
+                result: None,
+            },
             Example {
                 example: "ue start --server --log",
                 description: "Start a server for the project",
@@ -105,7 +115,7 @@ impl PluginCommand for UEStart {
 
         let uproject = uproject::UProject::from_path(&uproject_path)?;
         let unreal_editor_path =
-            ue_paths::get_unreal_editor_path(uproject.get_unreal_engine_path_str()?);
+            ue_paths::get_unreal_editor_path(uproject.get_unreal_engine_path_str()?)?;
         let mut command = Command::new(&unreal_editor_path);
 
         command
@@ -151,6 +161,12 @@ impl PluginCommand for UEStart {
 
         command.args(&args);
 
-        ue_tools::run(&mut command, call.head)
+        // The editor and the game run for a long time, their log is streamed as it is written
+        ue_tools::run_streaming(
+            &mut command,
+            call.head,
+            engine.signals(),
+            !call.has_flag("no-fail")?,
+        )
     }
 }
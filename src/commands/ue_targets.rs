@@ -0,0 +1,68 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, SyntaxShape, Value};
+
+use crate::{
+    UnrealEnginePlugin,
+    utils::{rules, uproject},
+};
+
+pub struct UETargets;
+
+impl SimplePluginCommand for UETargets {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "ue targets"
+    }
+
+    fn description(&self) -> &str {
+        "List the build targets of the project and its engine from the *.Target.cs files"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .named(
+                "uproject",
+                SyntaxShape::Filepath,
+                "Path to a uproject, default is the .uproject file of the current directory",
+                Some('u'),
+            )
+            .category(Category::Plugin)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "ue targets",
+                description: "List the build targets",
+                result: None,
+            },
+            Example {
+                example: "ue targets | where not is_engine and type == Editor",
+                description: "Find the editor target of the project",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let uproject_path =
+            uproject::uproject_from_arg_or_current_dir(&engine, call.get_flag("uproject")?)?;
+        let uproject = uproject::UProject::from_path(&uproject_path)?;
+
+        let targets = rules::find_targets(
+            &uproject_path.parent().unwrap().to_path_buf(),
+            uproject.unreal_engine_path.as_ref(),
+        )?;
+        Ok(Value::list(
+            targets.iter().map(|t| t.to_value(call.head)).collect(),
+            call.head,
+        ))
+    }
+}
@@ -0,0 +1,299 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::LazyLock,
+};
+
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, SyntaxShape, Value, record};
+use regex::Regex;
+
+use crate::{
+    UnrealEnginePlugin,
+    utils::{asset_scan, ue_config, ue_ini::IniValue, uproject},
+};
+
+pub struct UEUnusedAssets;
+
+// Keys of DefaultEngine.ini and DefaultGame.ini referencing assets that are always cooked
+const ROOT_KEYS: &[&str] = &[
+    "GameDefaultMap",
+    "EditorStartupMap",
+    "ServerDefaultMap",
+    "TransitionMap",
+    "GlobalDefaultGameMode",
+    "GlobalDefaultServerGameMode",
+    "GameInstanceClass",
+    "MapsToCook",
+    "DirectoriesToAlwaysCook",
+    "PrimaryAssetTypesToScan",
+];
+
+static GAME_PATH_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"/Game/[\w\-/.]*").unwrap());
+
+// Add the packages and directories referenced by a value, the structs can be nested:
+// PrimaryAssetTypesToScan=(PrimaryAssetType="Map",Directories=((Path="/Game/Maps")))
+fn add_value_roots(value: &IniValue, member: Option<&str>, roots: &mut Vec<String>) {
+    match value {
+        IniValue::String(string) => {
+            for path in GAME_PATH_REGEX.find_iter(string) {
+                // Remove the object name: /Game/Maps/Main.Main or /Game/BP_GameMode.BP_GameMode_C
+                let package = path
+                    .as_str()
+                    .split('.')
+                    .next()
+                    .unwrap()
+                    .trim_end_matches('/');
+                // Directories are set with (Path="/Game/UI"), maps with (FilePath="/Game/Maps/Main")
+                if member == Some("Path") {
+                    roots.push(format!("{}/", package));
+                } else {
+                    roots.push(package.to_string());
+                }
+            }
+        }
+        IniValue::Struct(members) => {
+            for (name, value) in members {
+                add_value_roots(value, Some(name), roots);
+            }
+        }
+        IniValue::Array(items) => {
+            for item in items {
+                add_value_roots(item, member, roots);
+            }
+        }
+    }
+}
+
+// Roots are either packages (/Game/Maps/Main) or directories (/Game/UI/).
+// The -Key= and !Key= lines of the files are applied, they remove roots.
+fn get_config_roots(project_dir: &PathBuf) -> Result<Vec<String>, LabeledError> {
+    let mut roots = vec![];
+    for ini in ["Config/DefaultEngine.ini", "Config/DefaultGame.ini"] {
+        for section in ue_config::merge_config_files(&[project_dir.join(ini)])? {
+            for value in section
+                .values
+                .iter()
+                .filter(|value| ROOT_KEYS.contains(&value.key.as_str()))
+            {
+                add_value_roots(&value.value, None, &mut roots);
+            }
+        }
+    }
+    Ok(roots)
+}
+
+// External actors and objects of a map are stored in /Game/__ExternalActors__/<Map>/<XX>/<YY>/<Hash>
+fn get_external_package_owner(package_name: &str) -> Option<String> {
+    for folder in ["/__ExternalActors__/", "/__ExternalObjects__/"] {
+        if let Some((mount_point, path)) = package_name.split_once(folder) {
+            let segments: Vec<&str> = path.split('/').collect();
+            if segments.len() <= 3 {
+                return None;
+            }
+            return Some(format!(
+                "{}/{}",
+                mount_point,
+                segments[..segments.len() - 3].join("/")
+            ));
+        }
+    }
+    None
+}
+
+impl SimplePluginCommand for UEUnusedAssets {
+    type Plugin = UnrealEnginePlugin;
+
+    fn name(&self) -> &str {
+        "ue unused-assets"
+    }
+
+    fn description(&self) -> &str {
+        "List the packages under /Game not reachable from the maps and directories to cook of DefaultEngine.ini and DefaultGame.ini"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .named(
+                "uproject",
+                SyntaxShape::Filepath,
+                "Path to a uproject, default is the .uproject file of the current directory",
+                Some('u'),
+            )
+            .named(
+                "roots",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "Additional root packages, or directories ending with /",
+                Some('r'),
+            )
+            .category(Category::Plugin)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: "ue unused-assets | sort-by size --reverse",
+                description: "List the unused packages, biggest first",
+                result: None,
+            },
+            Example {
+                example: "ue unused-assets --roots [/Game/Maps/Test /Game/Cinematics/]",
+                description: "List the unused packages with additional roots",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &UnrealEnginePlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let uproject_path =
+            uproject::uproject_from_arg_or_current_dir(&engine, call.get_flag("uproject")?)?;
+        let project_dir = uproject_path.parent().unwrap().to_path_buf();
+
+        let mut roots = get_config_roots(&project_dir)?;
+        roots.extend(call.get_flag::<Vec<String>>("roots")?.unwrap_or_default());
+
+        let packages = asset_scan::open_package_files(&project_dir);
+        let mut dependencies: HashMap<String, Vec<String>> = HashMap::new();
+        for package in &packages {
+            // Packages whose dependencies cannot be read are skipped with a warning
+            let package_dependencies = match package.dependencies() {
+                Ok(package_dependencies) => package_dependencies,
+                Err(e) => {
+                    asset_scan::warn_skipped_package(&e);
+                    continue;
+                }
+            };
+            dependencies
+                .entry(package.package_name.clone())
+                .or_default()
+                .extend(
+                    package_dependencies
+                        .hard
+                        .into_iter()
+                        .chain(package_dependencies.soft),
+                );
+            // External actors are not imported by their map, but cooked with it
+            if let Some(owner) = get_external_package_owner(&package.package_name) {
+                dependencies
+                    .entry(owner)
+                    .or_default()
+                    .push(package.package_name.clone());
+            }
+        }
+
+        // Walk the dependencies from the roots
+        let mut reachable: HashSet<String> = HashSet::new();
+        let mut to_visit: Vec<String> = packages
+            .iter()
+            .filter(|package| {
+                roots.iter().any(|root| {
+                    package.package_name == *root
+                        || (root.ends_with('/') && package.package_name.starts_with(root.as_str()))
+                })
+            })
+            .map(|package| package.package_name.clone())
+            .collect();
+        while let Some(package_name) = to_visit.pop() {
+            if !reachable.insert(package_name.clone()) {
+                continue;
+            }
+            if let Some(package_dependencies) = dependencies.get(&package_name) {
+                to_visit.extend(
+                    package_dependencies
+                        .iter()
+                        .filter(|dependency| !reachable.contains(*dependency))
+                        .cloned(),
+                );
+            }
+        }
+
+        let unused = packages
+            .iter()
+            .filter(|package| {
+                package.package_name.starts_with("/Game/")
+                    && !reachable.contains(&package.package_name)
+            })
+            .map(|package| {
+                Value::record(
+                    record! {
+                        "package" => Value::string(&package.package_name, call.head),
+                        "path" => Value::string(package.path.display().to_string(), call.head),
+                        "size" => Value::filesize(package.size as i64, call.head),
+                    },
+                    call.head,
+                )
+            })
+            .collect();
+        Ok(Value::list(unused, call.head))
+    }
+}
+
+#[test]
+fn test_get_config_roots() {
+    let project_dir = std::env::temp_dir().join("nu_plugin_ue_test_get_config_roots");
+    std::fs::create_dir_all(project_dir.join("Config")).unwrap();
+    std::fs::write(
+        project_dir.join("Config/DefaultEngine.ini"),
+        r#"[/Script/EngineSettings.GameMapsSettings]
+GameDefaultMap=/Game/Maps/Main.Main
+EditorStartupMap=/Game/Maps/Main.Main
+GlobalDefaultGameMode=/Game/Blueprints/BP_GameMode.BP_GameMode_C
+; ServerDefaultMap=/Game/Maps/Server.Server
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        project_dir.join("Config/DefaultGame.ini"),
+        r#"[/Script/UnrealEd.ProjectPackagingSettings]
++MapsToCook=(FilePath="/Game/Maps/Main")
++MapsToCook=(FilePath="/Game/Maps/Test")
+-MapsToCook=(FilePath="/Game/Maps/Test")
++DirectoriesToAlwaysCook=(Path="/Game/Debug")
+!DirectoriesToAlwaysCook=ClearArray
++DirectoriesToAlwaysCook=(Path="/Game/UI")
+
+[/Script/Engine.AssetManagerSettings]
++PrimaryAssetTypesToScan=(PrimaryAssetType="Map",AssetBaseClass=/Script/Engine.World,bHasBlueprintClasses=False,bIsEditorOnly=True,Directories=((Path="/Game/Maps")),SpecificAssets=,Rules=(Priority=-1,ChunkId=-1,bApplyRecursively=True,CookRule=Unknown))
+"#,
+    )
+    .unwrap();
+
+    let roots = get_config_roots(&project_dir).unwrap();
+    std::fs::remove_dir_all(&project_dir).unwrap();
+    assert_eq!(
+        roots,
+        [
+            "/Game/Maps/Main",
+            "/Game/Maps/Main",
+            "/Game/Blueprints/BP_GameMode",
+            "/Game/Maps/Main",
+            "/Game/UI/",
+            "/Game/Maps/",
+        ]
+    );
+}
+
+#[test]
+fn test_get_external_package_owner() {
+    assert_eq!(
+        get_external_package_owner(
+            "/Game/__ExternalActors__/Maps/Main/0/AB/4TUV9XJ8KZ2M1Q7W3NBC5D"
+        ),
+        Some("/Game/Maps/Main".to_string())
+    );
+    assert_eq!(
+        get_external_package_owner("/Inventory/__ExternalObjects__/Maps/Shop/C/3F/A1B2C3"),
+        Some("/Inventory/Maps/Shop".to_string())
+    );
+    assert_eq!(
+        get_external_package_owner("/Game/__ExternalActors__/0/AB/4TUV9XJ8KZ2M1Q7W3NBC5D"),
+        None
+    );
+    assert_eq!(get_external_package_owner("/Game/Maps/Main"), None);
+}
@@ -15,10 +15,28 @@ impl Plugin for UnrealEnginePlugin {
     fn commands(&self) -> Vec<Box<dyn PluginCommand<Plugin = Self>>> {
         vec![
             Box::new(FromUAsset),
+            Box::new(FromUEIni),
+            Box::new(ToUEIni),
+            Box::new(FromUMap),
+            Box::new(FromUPlugin),
             Box::new(UE),
+            Box::new(UEAssetDeps),
+            Box::new(UEAssetDiff),
             Box::new(UEBuild),
+            Box::new(UEConfig),
+            Box::new(UEConfigSet),
+            Box::new(UEEngines),
+            Box::new(UELevelActors),
+            Box::new(UEModules),
+            Box::new(UEPluginInfo),
+            Box::new(UEPlugins),
+            Box::new(UEPluginsDisable),
+            Box::new(UEPluginsEnable),
+            Box::new(UERedirectors),
             Box::new(UERunUAT),
             Box::new(UEStart),
+            Box::new(UETargets),
+            Box::new(UEUnusedAssets),
             Box::new(UEGenerateProjectFiles),
             Box::new(UERegisterEngine),
             Box::new(UESwitchVersion),
@@ -0,0 +1,145 @@
+use std::{
+    fs::File,
+    io::{BufReader, Cursor, Read, Seek, SeekFrom},
+    path::PathBuf,
+};
+
+use nu_protocol::LabeledError;
+use uasset::AssetHeader;
+
+use super::{
+    uasset_reader::{self, ObjectExport},
+    ue_paths,
+};
+
+/**
+ * A .uasset or .umap file of the project, only the header is loaded since the exports data can be large
+ */
+pub struct PackageFile {
+    pub path: PathBuf,
+    pub package_name: String,
+    pub size: u64,
+    pub header_bytes: Vec<u8>,
+}
+
+impl PackageFile {
+    pub fn open(path: &PathBuf) -> Result<PackageFile, LabeledError> {
+        let to_error = |e: &dyn std::fmt::Display| {
+            LabeledError::new(format!("Failed to read {}: {}", path.display(), e))
+        };
+        let file = File::open(path).map_err(|e| to_error(&e))?;
+        let size = file.metadata().map_err(|e| to_error(&e))?.len();
+        let header = AssetHeader::new(BufReader::new(&file)).map_err(|e| to_error(&e))?;
+
+        let mut header_bytes = vec![];
+        File::open(path)
+            .map_err(|e| to_error(&e))?
+            .take(header.total_header_size as u64)
+            .read_to_end(&mut header_bytes)
+            .map_err(|e| to_error(&e))?;
+
+        Ok(PackageFile {
+            path: path.clone(),
+            package_name: ue_paths::get_package_name(path).unwrap_or_default(),
+            size,
+            header_bytes,
+        })
+    }
+
+    pub fn header(&self) -> Result<AssetHeader<Cursor<&[u8]>>, LabeledError> {
+        AssetHeader::new(Cursor::new(self.header_bytes.as_slice())).map_err(|e| {
+            LabeledError::new(format!("Failed to read {}: {}", self.path.display(), e))
+        })
+    }
+
+    /**
+     * Read the serialized data of an export from the file, it follows the header
+     */
+    pub fn read_export_data(&self, export: &ObjectExport) -> Result<Vec<u8>, LabeledError> {
+        let to_error = |e: &dyn std::fmt::Display| {
+            LabeledError::new(format!("Failed to read {}: {}", self.path.display(), e))
+        };
+        if export.serial_offset < 0
+            || export.serial_size < 0
+            || (export.serial_offset + export.serial_size) as u64 > self.size
+        {
+            return Err(to_error(&format!(
+                "invalid export data at offset {} of {} bytes",
+                export.serial_offset, export.serial_size
+            )));
+        }
+        let mut file = File::open(&self.path).map_err(|e| to_error(&e))?;
+        file.seek(SeekFrom::Start(export.serial_offset as u64))
+            .map_err(|e| to_error(&e))?;
+        let mut data = vec![0; export.serial_size as usize];
+        file.read_exact(&mut data).map_err(|e| to_error(&e))?;
+        Ok(data)
+    }
+
+    /**
+     * Get the packages imported by the package (hard) and referenced by its soft package references and soft object paths (soft)
+     */
+    pub fn dependencies(&self) -> Result<PackageDependencies, LabeledError> {
+        let header = self.header()?;
+        let soft = uasset_reader::read_soft_package_dependencies(&self.header_bytes, &header)
+            .map_err(|e| {
+                LabeledError::new(format!("Failed to read {}: {}", self.path.display(), e.msg))
+            })?;
+        Ok(PackageDependencies {
+            hard: uasset_reader::read_package_imports(&header),
+            soft,
+        })
+    }
+}
+
+pub struct PackageDependencies {
+    pub hard: Vec<String>,
+    pub soft: Vec<String>,
+}
+
+/**
+ * Report a package or a plugin skipped by a scan on stderr, the stdout of the plugin is the channel of the plugin protocol
+ */
+pub fn warn_skipped_package(error: &LabeledError) {
+    eprintln!("Warning: {}", error.msg);
+}
+
+/**
+ * Returns the Content directories of the project and its plugins
+ */
+pub fn get_content_directories(project_dir: &PathBuf) -> Vec<PathBuf> {
+    let mut directories = vec![project_dir.join("Content")];
+    directories.extend(
+        ue_paths::find_files(&project_dir.join("Plugins"), &[".uplugin"])
+            .iter()
+            .map(|uplugin| uplugin.parent().unwrap().join("Content"))
+            .filter(|content| content.exists()),
+    );
+    directories
+}
+
+/**
+ * Returns the .uasset and .umap files of the project and its plugins
+ */
+pub fn find_package_files(project_dir: &PathBuf) -> Vec<PathBuf> {
+    get_content_directories(project_dir)
+        .iter()
+        .flat_map(|directory| ue_paths::find_files(directory, &[".uasset", ".umap"]))
+        .collect()
+}
+
+/**
+ * Open the packages of the project, packages that cannot be read are skipped with a warning on stderr
+ */
+pub fn open_package_files(project_dir: &PathBuf) -> Vec<PackageFile> {
+    find_package_files(project_dir)
+        .iter()
+        .filter_map(|path| match PackageFile::open(path) {
+            Ok(package) => Some(package),
+            Err(e) => {
+                warn_skipped_package(&e);
+                None
+            }
+        })
+        .collect()
+}
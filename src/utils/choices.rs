@@ -0,0 +1,21 @@
+use nu_protocol::{LabeledError, Spanned, did_you_mean};
+
+/**
+ * Get the value with the case of the choices, e.g. win64 is Win64, or an error suggesting the closest choice
+ */
+pub fn validate_choice(
+    value: Spanned<String>,
+    choices: &[&str],
+    kind: &str,
+) -> Result<String, LabeledError> {
+    if let Some(choice) = choices.iter().find(|c| c.eq_ignore_ascii_case(&value.item)) {
+        return Ok(choice.to_string());
+    }
+    let mut error = LabeledError::new(format!("Unknown {} '{}'", kind, value.item))
+        .with_label(format!("unknown {}", kind), value.span);
+    error = match did_you_mean(choices, &value.item) {
+        Some(suggestion) => error.with_help(format!("Did you mean '{}'?", suggestion)),
+        None => error.with_help(format!("Expected one of: {}", choices.join(", "))),
+    };
+    Err(error)
+}
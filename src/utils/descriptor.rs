@@ -0,0 +1,319 @@
+use std::path::PathBuf;
+
+use nu_protocol::{LabeledError, Span, Value, record};
+
+// https://dev.epicgames.com/documentation/en-us/unreal-engine/API/Runtime/Projects/FModuleDescriptor
+
+// EHostType
+const MODULE_TYPES: &[&str] = &[
+    "Runtime",
+    "RuntimeNoCommandlet",
+    "RuntimeAndProgram",
+    "CookedOnly",
+    "UncookedOnly",
+    "Developer",
+    "DeveloperTool",
+    "Editor",
+    "EditorNoCommandlet",
+    "EditorAndProgram",
+    "Program",
+    "ServerOnly",
+    "ClientOnly",
+    "ClientOnlyNoCommandlet",
+];
+
+// ELoadingPhase
+const LOADING_PHASES: &[&str] = &[
+    "EarliestPossible",
+    "PostConfigInit",
+    "PostSplashScreen",
+    "PreEarlyLoadingScreen",
+    "PreLoadingScreen",
+    "PreDefault",
+    "Default",
+    "PostDefault",
+    "PostEngineInit",
+    "None",
+];
+
+pub struct ModuleDescriptor {
+    pub name: String,
+    pub module_type: String,
+    pub loading_phase: String,
+    pub platform_allow_list: Vec<String>,
+}
+
+// https://dev.epicgames.com/documentation/en-us/unreal-engine/API/Runtime/Projects/FPluginReferenceDescriptor
+
+pub struct PluginReference {
+    pub name: String,
+    pub enabled: bool,
+    pub optional: bool,
+    pub marketplace_url: Option<String>,
+    pub target_allow_list: Vec<String>,
+}
+
+pub fn json_string(object: &serde_json::Value, key: &str) -> Option<String> {
+    object
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string())
+}
+
+pub fn json_string_list(object: &serde_json::Value, key: &str) -> Vec<String> {
+    object
+        .get(key)
+        .and_then(|v| v.as_array())
+        .map(|list| {
+            list.iter()
+                .filter_map(|v| v.as_str().map(|v| v.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn json_object_list<'a>(object: &'a serde_json::Value, key: &str) -> &'a [serde_json::Value] {
+    object
+        .get(key)
+        .and_then(|v| v.as_array())
+        .map(|list| list.as_slice())
+        .unwrap_or_default()
+}
+
+impl ModuleDescriptor {
+    pub fn from_json(object: &serde_json::Value) -> Result<ModuleDescriptor, LabeledError> {
+        let name = json_string(object, "Name")
+            .ok_or(LabeledError::new("Module descriptor is missing 'Name'"))?;
+        // The engine reads the Type and LoadingPhase case insensitively.
+        // Unknown values, e.g. of a newer engine, are kept as written with a warning on stderr.
+        let known_value = |key: &str, value: String, choices: &[&str]| match choices
+            .iter()
+            .find(|choice| choice.eq_ignore_ascii_case(&value))
+        {
+            Some(choice) => choice.to_string(),
+            None => {
+                eprintln!(
+                    "Warning: Module descriptor {} has an unknown '{}' {}, expected one of: {}",
+                    name,
+                    key,
+                    value,
+                    choices.join(", ")
+                );
+                value
+            }
+        };
+        let module_type = json_string(object, "Type").ok_or(LabeledError::new(format!(
+            "Module descriptor {} is missing 'Type'",
+            name
+        )))?;
+        let module_type = known_value("Type", module_type, MODULE_TYPES);
+        let loading_phase = match json_string(object, "LoadingPhase") {
+            Some(loading_phase) => known_value("LoadingPhase", loading_phase, LOADING_PHASES),
+            None => "Default".to_string(),
+        };
+        // WhitelistPlatforms was renamed PlatformAllowList in UE5
+        let mut platform_allow_list = json_string_list(object, "PlatformAllowList");
+        platform_allow_list.extend(json_string_list(object, "WhitelistPlatforms"));
+
+        Ok(ModuleDescriptor {
+            name,
+            module_type,
+            loading_phase,
+            platform_allow_list,
+        })
+    }
+
+    pub fn to_value(&self, span: Span) -> Value {
+        Value::record(
+            record! {
+                "name" => Value::string(&self.name, span),
+                "type" => Value::string(&self.module_type, span),
+                "loading_phase" => Value::string(&self.loading_phase, span),
+                "platform_allow_list" => Value::list(self.platform_allow_list.iter().map(|p| Value::string(p, span)).collect(), span),
+            },
+            span,
+        )
+    }
+}
+
+impl PluginReference {
+    pub fn from_json(object: &serde_json::Value) -> Result<PluginReference, LabeledError> {
+        let name = json_string(object, "Name")
+            .ok_or(LabeledError::new("Plugin reference is missing 'Name'"))?;
+        // WhitelistTargets was renamed TargetAllowList in UE5
+        let mut target_allow_list = json_string_list(object, "TargetAllowList");
+        target_allow_list.extend(json_string_list(object, "WhitelistTargets"));
+
+        Ok(PluginReference {
+            name,
+            enabled: object
+                .get("Enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            optional: object
+                .get("Optional")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            marketplace_url: json_string(object, "MarketplaceURL"),
+            target_allow_list,
+        })
+    }
+
+    pub fn to_value(&self, span: Span) -> Value {
+        Value::record(
+            record! {
+                "name" => Value::string(&self.name, span),
+                "enabled" => Value::bool(self.enabled, span),
+                "optional" => Value::bool(self.optional, span),
+                "marketplace_url" => self.marketplace_url.as_ref().map(|u| Value::string(u, span)).unwrap_or_default(),
+                "target_allow_list" => Value::list(self.target_allow_list.iter().map(|t| Value::string(t, span)).collect(), span),
+            },
+            span,
+        )
+    }
+}
+
+/**
+ * Read the Modules of a .uproject or .uplugin, a module without Name or Type is an error
+ */
+pub fn read_modules(object: &serde_json::Value) -> Result<Vec<ModuleDescriptor>, LabeledError> {
+    json_object_list(object, "Modules")
+        .iter()
+        .map(ModuleDescriptor::from_json)
+        .collect()
+}
+
+/**
+ * Read the Plugins of a .uproject, or the plugin dependencies of a .uplugin
+ */
+pub fn read_plugin_references(
+    object: &serde_json::Value,
+) -> Result<Vec<PluginReference>, LabeledError> {
+    json_object_list(object, "Plugins")
+        .iter()
+        .map(PluginReference::from_json)
+        .collect()
+}
+
+/**
+ * Read a .uproject or .uplugin file
+ */
+pub fn read_json(path: &PathBuf) -> Result<serde_json::Value, LabeledError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| LabeledError::new(format!("Failed to read {}: {}", path.display(), e)))?;
+    serde_json::from_str(content.trim_start_matches('\u{feff}'))
+        .map_err(|e| LabeledError::new(format!("Failed to parse {}: {}", path.display(), e)))
+}
+
+#[test]
+fn test_read_modules() {
+    let modules = r#"[
+        {
+            "Name": "MyGame",
+            "Type": "Runtime",
+            "LoadingPhase": "Default",
+            "AdditionalDependencies": ["Engine"]
+        },
+        {
+            "Name": "MyGameEditor",
+            "Type": "editor",
+            "LoadingPhase": "PostEngineInit",
+            "WhitelistPlatforms": ["Win64"]
+        },
+        {
+            "Name": "MyGameTests",
+            "Type": "DeveloperTool",
+            "PlatformAllowList": ["Win64", "Linux"]
+        }
+    ]"#;
+    let uproject: serde_json::Value = serde_json::from_str(&format!(
+        r#"{{"FileVersion": 3, "EngineAssociation": "5.4", "Modules": {}}}"#,
+        modules
+    ))
+    .unwrap();
+    let uplugin: serde_json::Value = serde_json::from_str(&format!(
+        r#"{{"FileVersion": 3, "FriendlyName": "My Game", "Modules": {}}}"#,
+        modules
+    ))
+    .unwrap();
+
+    let project_modules = super::uproject::ProjectDescriptor::from_json(&uproject)
+        .unwrap()
+        .modules;
+    let plugin_modules = super::uplugin::PluginDescriptor::from_json(&uplugin)
+        .unwrap()
+        .modules;
+    for modules in [project_modules, plugin_modules] {
+        let modules: Vec<(&str, &str, &str, Vec<String>)> = modules
+            .iter()
+            .map(|module| {
+                (
+                    module.name.as_str(),
+                    module.module_type.as_str(),
+                    module.loading_phase.as_str(),
+                    module.platform_allow_list.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            modules,
+            vec![
+                ("MyGame", "Runtime", "Default", vec![]),
+                (
+                    "MyGameEditor",
+                    "Editor",
+                    "PostEngineInit",
+                    vec!["Win64".to_string()]
+                ),
+                (
+                    "MyGameTests",
+                    "DeveloperTool",
+                    "Default",
+                    vec!["Win64".to_string(), "Linux".to_string()]
+                ),
+            ]
+        );
+    }
+
+    // Unknown values are kept as written, a module without Type is an error
+    let unknown: serde_json::Value = serde_json::from_str(
+        r#"{"Modules": [{"Name": "MyGame", "Type": "Game", "LoadingPhase": "Later"}]}"#,
+    )
+    .unwrap();
+    let modules = read_modules(&unknown).unwrap();
+    assert_eq!(modules[0].module_type, "Game");
+    assert_eq!(modules[0].loading_phase, "Later");
+    let invalid: serde_json::Value =
+        serde_json::from_str(r#"{"Modules": [{"Name": "MyGame"}]}"#).unwrap();
+    assert!(read_modules(&invalid).is_err());
+}
+
+#[test]
+fn test_plugin_reference_optional() {
+    let plugins: serde_json::Value = serde_json::from_str(
+        r#"{"Plugins": [
+            {"Name": "OnlineSubsystemSteam", "Enabled": true, "Optional": true},
+            {"Name": "ModelingToolsEditorMode", "Enabled": true, "TargetAllowList": ["Editor"]}
+        ]}"#,
+    )
+    .unwrap();
+    let plugins = read_plugin_references(&plugins).unwrap();
+    assert!(plugins[0].optional);
+    assert!(!plugins[1].optional);
+
+    // The optional field is listed by `ue` and `ue plugin-info`
+    let span = Span::test_data();
+    let optional = |reference: &PluginReference| {
+        reference
+            .to_value(span)
+            .as_record()
+            .unwrap()
+            .get("optional")
+            .unwrap()
+            .as_bool()
+            .unwrap()
+    };
+    assert!(optional(&plugins[0]));
+    assert!(!optional(&plugins[1]));
+    assert_eq!(plugins[1].target_allow_list, vec!["Editor"]);
+}
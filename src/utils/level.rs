@@ -0,0 +1,289 @@
+use std::path::PathBuf;
+
+use nu_protocol::{LabeledError, Span, Value, record};
+
+use super::{
+    asset_scan::{self, PackageFile},
+    uasset_reader::{self, ObjectResolver},
+    ue_paths,
+};
+
+// Objects of the level that are not actors
+const NON_ACTOR_CLASSES: &[&str] = &[
+    "/Script/Engine.Model",
+    "/Script/Engine.Polys",
+    "/Script/Engine.NavigationObjectRepository",
+    "/Script/Engine.WorldPartition",
+    "/Script/Engine.LevelActorContainer",
+    "/Script/Engine.LevelScriptBlueprint",
+    "/Script/Engine.BlueprintGeneratedClass",
+    "/Script/Engine.ActorFolder",
+];
+
+// EObjectFlags
+const RF_CLASS_DEFAULT_OBJECT: u32 = 0x10;
+
+/**
+ * Actor of a level, saved in the .umap or in its own package with One File Per Actor
+ */
+pub struct LevelActor {
+    pub name: String,
+    pub class: String,
+    // e.g. /Game/Maps/Main.Main:PersistentLevel
+    pub outer: String,
+    pub package: String,
+    pub external: bool,
+}
+
+impl LevelActor {
+    pub fn to_value(&self, span: Span) -> Value {
+        Value::record(
+            record! {
+                "name" => Value::string(&self.name, span),
+                "class" => Value::string(&self.class, span),
+                "outer" => Value::string(&self.outer, span),
+                "package" => Value::string(&self.package, span),
+                "external" => Value::bool(self.external, span),
+            },
+            span,
+        )
+    }
+}
+
+/**
+ * Get the actors of a package: the exports whose outer is the PersistentLevel, exported by a .umap or imported by an external actor
+ */
+pub fn read_level_actors<R: std::io::Read>(
+    resolver: &ObjectResolver<R>,
+    external: bool,
+) -> Vec<LevelActor> {
+    let names = &resolver.header.names;
+    resolver
+        .exports
+        .iter()
+        .enumerate()
+        .filter(|(_, export)| {
+            uasset_reader::resolve_object_name(
+                export.outer_index,
+                resolver.header,
+                resolver.exports,
+            )
+            .is_some_and(|outer| outer == "PersistentLevel")
+                && resolver
+                    .class_path(export.outer_index)
+                    .is_some_and(|class| class == "/Script/Engine.Level")
+        })
+        .filter_map(|(index, export)| {
+            let class = resolver.class_path(index as i32 + 1)?;
+            // The default objects of the level script and of the blueprints are not placed in the level
+            if NON_ACTOR_CLASSES.contains(&class.as_str())
+                || export.object_flags & RF_CLASS_DEFAULT_OBJECT != 0
+            {
+                return None;
+            }
+            Some(LevelActor {
+                name: export.object_name.resolve(names),
+                class,
+                outer: resolver.object_path(export.outer_index)?,
+                package: resolver.package_name.unwrap_or_default().to_string(),
+                external,
+            })
+        })
+        .collect()
+}
+
+/**
+ * Get the actors of a map and of its external actor packages, the external packages that cannot be read are skipped with a warning
+ */
+pub fn read_map_actors(
+    bytes: &[u8],
+    map_path: Option<&PathBuf>,
+) -> Result<Vec<LevelActor>, LabeledError> {
+    let header = uasset::AssetHeader::new(std::io::Cursor::new(bytes))
+        .map_err(|e| LabeledError::new(e.to_string()))?;
+    let exports = uasset_reader::read_exports(bytes, &header)?;
+    let package_name = map_path.and_then(ue_paths::get_package_name);
+    let resolver = ObjectResolver {
+        header: &header,
+        exports: &exports,
+        package_name: package_name.as_deref(),
+    };
+    let mut actors = read_level_actors(&resolver, false);
+
+    let Some(directory) = map_path.and_then(ue_paths::get_external_actors_directory) else {
+        return Ok(actors);
+    };
+    for path in ue_paths::find_files(&directory, &[".uasset"]) {
+        match read_external_actors(&path) {
+            Ok(external_actors) => actors.extend(external_actors),
+            Err(e) => asset_scan::warn_skipped_package(&e),
+        }
+    }
+    Ok(actors)
+}
+
+fn read_external_actors(path: &PathBuf) -> Result<Vec<LevelActor>, LabeledError> {
+    let package = PackageFile::open(path)?;
+    let header = package.header()?;
+    let exports = uasset_reader::read_exports(&package.header_bytes, &header)?;
+    let resolver = ObjectResolver {
+        header: &header,
+        exports: &exports,
+        package_name: Some(&package.package_name),
+    };
+    Ok(read_level_actors(&resolver, true))
+}
+
+/**
+ * Actor saved in its own package with One File Per Actor, e.g. Content/__ExternalActors__/Maps/Main/0/AB/XYZ.uasset
+ */
+pub struct ExternalActor {
+    pub path: PathBuf,
+    pub package: String,
+    pub name: String,
+    pub class: String,
+    pub data_layers: Vec<String>,
+}
+
+impl ExternalActor {
+    pub fn to_value(&self, directory: &PathBuf, span: Span) -> Value {
+        let file = self.path.strip_prefix(directory).unwrap_or(&self.path);
+        Value::record(
+            record! {
+                "file" => Value::string(file.display().to_string(), span),
+                "package" => Value::string(&self.package, span),
+                "name" => Value::string(&self.name, span),
+                "class" => Value::string(&self.class, span),
+                "data_layers" => Value::list(
+                    self.data_layers.iter().map(|layer| Value::string(layer, span)).collect(),
+                    span,
+                ),
+            },
+            span,
+        )
+    }
+}
+
+/**
+ * Read the actor of an external actor package, the data layers are the DataLayerAsset imported by the package.
+ * The label of the actor is not read, it is serialized in the FWorldPartitionActorDesc of the asset registry.
+ */
+pub fn read_external_actor(path: &PathBuf) -> Result<ExternalActor, LabeledError> {
+    let package = PackageFile::open(path)?;
+    let header = package.header()?;
+    let exports = uasset_reader::read_exports(&package.header_bytes, &header)?;
+    let resolver = ObjectResolver {
+        header: &header,
+        exports: &exports,
+        package_name: Some(&package.package_name),
+    };
+
+    let actor = read_level_actors(&resolver, true)
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            LabeledError::new(format!(
+                "Failed to read {}: no actor in the package",
+                path.display()
+            ))
+        })?;
+    let data_layers = (0..header.imports.len() as i32)
+        .map(|index| -index - 1)
+        .filter(|&index| {
+            resolver
+                .class_path(index)
+                .is_some_and(|class| class == "/Script/Engine.DataLayerAsset")
+        })
+        .filter_map(|index| resolver.object_path(index))
+        .collect();
+
+    Ok(ExternalActor {
+        path: path.clone(),
+        package: package.package_name,
+        name: actor.name,
+        class: actor.class,
+        data_layers,
+    })
+}
+
+#[test]
+fn test_read_map_actors() {
+    let map_path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/Content/Maps/Main.umap");
+    let bytes = std::fs::read(&map_path).unwrap();
+    let actors = read_map_actors(&bytes, Some(&map_path)).unwrap();
+
+    // The Model of the level, the folders, the components, the streaming levels
+    // and the level script blueprint, class and default object are not actors
+    let names: Vec<&str> = actors.iter().map(|actor| actor.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "WorldSettings",
+            "StaticMeshActor_0",
+            "PlayerStart",
+            "Main_C_0",
+            "StaticMeshActor_UAID_00155DCC5E9E6C4D01_1234567890",
+        ]
+    );
+    assert_eq!(actors[1].class, "/Script/Engine.StaticMeshActor");
+    assert_eq!(actors[1].outer, "/Game/Maps/Main.Main:PersistentLevel");
+    assert_eq!(actors[1].package, "/Game/Maps/Main");
+    assert!(!actors[1].external);
+
+    // The level script actor is an instance of the class generated by the level script blueprint
+    assert_eq!(actors[3].class, "/Game/Maps/Main.Main_C");
+    assert_eq!(actors[3].outer, "/Game/Maps/Main.Main:PersistentLevel");
+
+    // The external actor is in the PersistentLevel imported from the map
+    assert_eq!(actors[4].class, "/Script/Engine.StaticMeshActor");
+    assert_eq!(actors[4].outer, "/Game/Maps/Main.Main:PersistentLevel");
+    assert_eq!(
+        actors[4].package,
+        "/Game/__ExternalActors__/Maps/Main/0/AB/4TUV9XJ8KZ2M1Q7W3NBC5D"
+    );
+    assert!(actors[4].external);
+}
+
+#[test]
+fn test_read_streaming_level_actors() {
+    let map_path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/Content/Maps/Main_Sub.umap");
+    let bytes = std::fs::read(&map_path).unwrap();
+    let actors = read_map_actors(&bytes, Some(&map_path)).unwrap();
+
+    let names: Vec<&str> = actors.iter().map(|actor| actor.name.as_str()).collect();
+    assert_eq!(names, vec!["WorldSettings", "PointLight_0"]);
+    assert_eq!(actors[1].class, "/Script/Engine.PointLight");
+    assert_eq!(
+        actors[1].outer,
+        "/Game/Maps/Main_Sub.Main_Sub:PersistentLevel"
+    );
+
+    // Without the path of the map, the external actors cannot be found
+    let bytes = include_bytes!("../../tests/fixtures/Content/Maps/Main.umap");
+    let actors = read_map_actors(bytes, None).unwrap();
+    assert_eq!(actors.len(), 4);
+    assert_eq!(actors[1].outer, "Main:PersistentLevel");
+}
+
+#[test]
+fn test_read_external_actor() {
+    let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/Content/__ExternalActors__/Maps/Main");
+    let actor = read_external_actor(&directory.join("0/AB/4TUV9XJ8KZ2M1Q7W3NBC5D.uasset")).unwrap();
+
+    assert_eq!(
+        actor.package,
+        "/Game/__ExternalActors__/Maps/Main/0/AB/4TUV9XJ8KZ2M1Q7W3NBC5D"
+    );
+    assert_eq!(
+        actor.name,
+        "StaticMeshActor_UAID_00155DCC5E9E6C4D01_1234567890"
+    );
+    assert_eq!(actor.class, "/Script/Engine.StaticMeshActor");
+    assert_eq!(
+        actor.data_layers,
+        vec!["/Game/DataLayers/DL_Night.DL_Night"]
+    );
+}
@@ -1,3 +1,14 @@
+pub mod asset_scan;
+pub mod choices;
+pub mod descriptor;
+pub mod level;
+pub mod rules;
+pub mod uasset_reader;
+pub mod ubt_diagnostics;
+pub mod ue_config;
+pub mod ue_engines;
+pub mod ue_ini;
 pub mod ue_paths;
 pub mod ue_tools;
+pub mod uplugin;
 pub mod uproject;
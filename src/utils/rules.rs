@@ -0,0 +1,493 @@
+use std::{path::PathBuf, sync::LazyLock};
+
+use nu_protocol::{LabeledError, Span, Value, record};
+use regex::{Captures, Regex};
+
+use super::{ue_engines, ue_paths};
+
+// https://dev.epicgames.com/documentation/en-us/unreal-engine/unreal-engine-build-tool-target-reference
+
+// The strings are matched first to keep the // and /* they contain
+static COMMENT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?s)@"(?:[^"]|"")*"|"(?:[^"\\\n]|\\.)*"|//[^\n]*|/\*.*?\*/"#).unwrap()
+});
+static QUOTED_STRING_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^"((?:[^"\\]|\\.)*)""#).unwrap());
+static ENUM_FIELD_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(\w+)\s*=\s*(\w+)\.(\w+)").unwrap());
+static LIST_ADD_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(\w+)\s*\.\s*(?:Add|AddRange)\s*\(").unwrap());
+static CLASS_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bclass\s+\w+").unwrap());
+static TARGET_CLASS_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bclass\s+(\w+)Target\s*:\s*TargetRules\b").unwrap());
+static MODULE_CLASS_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bclass\s+(\w+)\s*:\s*ModuleRules\b").unwrap());
+static PCH_HEADER_FILE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bPrivatePCHHeaderFile\s*=\s*"([^"]*)""#).unwrap());
+
+/**
+ * Read a C# rules file without its comments, the templates contain commented out rules
+ */
+pub fn read_rules_file(path: &PathBuf) -> Result<String, LabeledError> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        LabeledError::new(format!(
+            "Failed to read {}: {}",
+            path.display(),
+            e.to_string()
+        ))
+    })?;
+    Ok(strip_comments(&content))
+}
+
+fn strip_comments(content: &str) -> String {
+    COMMENT_REGEX
+        .replace_all(content, |cap: &Captures| {
+            if cap[0].starts_with('/') {
+                String::new()
+            } else {
+                cap[0].to_string()
+            }
+        })
+        .to_string()
+}
+
+/**
+ * Get the value of an enum assignment: `Field = Enum.Value;`
+ */
+pub fn parse_enum_field(content: &str, field: &str, enum_name: &str) -> Option<String> {
+    ENUM_FIELD_REGEX
+        .captures_iter(content)
+        .find(|cap| &cap[1] == field && &cap[2] == enum_name)
+        .map(|cap| cap[3].to_string())
+}
+
+// Get the strings of the arguments of a call up to its closing parenthesis, without the strings of the nested calls
+fn parse_call_strings(arguments: &str) -> Vec<String> {
+    let mut strings = vec![];
+    let mut depth = 0;
+    let mut index = 0;
+    while let Some(c) = arguments[index..].chars().next() {
+        match c {
+            '"' => {
+                let Some(cap) = QUOTED_STRING_REGEX.captures(&arguments[index..]) else {
+                    break;
+                };
+                if depth == 0 {
+                    strings.push(cap[1].to_string());
+                }
+                index += cap[0].len();
+                continue;
+            }
+            '(' => depth += 1,
+            ')' if depth == 0 => break,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        index += c.len_utf8();
+    }
+    strings
+}
+
+/**
+ * Get the strings added to a list: `Field.Add("A");` and `Field.AddRange(new string[] { "B", "C" });`.
+ * The paths built by a call like `Path.Combine(ModuleDirectory, "Public")` are not known and skipped.
+ */
+pub fn parse_string_list(content: &str, field: &str) -> Vec<String> {
+    LIST_ADD_REGEX
+        .captures_iter(content)
+        .filter(|cap| &cap[1] == field)
+        .flat_map(|cap| parse_call_strings(&content[cap.get(0).unwrap().end()..]))
+        .collect()
+}
+
+pub struct TargetRules {
+    pub name: String,
+    pub target_type: Option<String>,
+    pub default_build_settings: Option<String>,
+    pub include_order_version: Option<String>,
+    pub extra_module_names: Vec<String>,
+    pub path: PathBuf,
+    pub is_engine: bool,
+}
+
+impl TargetRules {
+    /**
+     * Read the targets of a Target.cs file, a file can declare several target classes
+     */
+    pub fn from_path(path: &PathBuf, is_engine: bool) -> Result<Vec<TargetRules>, LabeledError> {
+        let content = read_rules_file(path)?;
+        Ok(TargetRules::from_content(&content, path, is_engine))
+    }
+
+    fn from_content(content: &str, path: &PathBuf, is_engine: bool) -> Vec<TargetRules> {
+        // The fields of a target are read from its class, up to the next class of the file
+        let class_starts: Vec<usize> = CLASS_REGEX.find_iter(content).map(|m| m.start()).collect();
+        let mut targets: Vec<TargetRules> = TARGET_CLASS_REGEX
+            .captures_iter(content)
+            .map(|cap| {
+                let start = cap.get(0).unwrap().start();
+                let end = class_starts
+                    .iter()
+                    .find(|class_start| **class_start > start)
+                    .copied()
+                    .unwrap_or(content.len());
+                TargetRules::from_class(&cap[1], &content[start..end], path, is_engine)
+            })
+            .collect();
+
+        // The class is named <Target>Target, like the file <Target>.Target.cs
+        if targets.is_empty() {
+            let file_name = path.file_name().unwrap().to_string_lossy();
+            let name = file_name.trim_end_matches(".Target.cs");
+            targets.push(TargetRules::from_class(name, content, path, is_engine));
+        }
+        targets
+    }
+
+    fn from_class(name: &str, content: &str, path: &PathBuf, is_engine: bool) -> TargetRules {
+        TargetRules {
+            name: name.to_string(),
+            target_type: parse_enum_field(content, "Type", "TargetType"),
+            default_build_settings: parse_enum_field(
+                content,
+                "DefaultBuildSettings",
+                "BuildSettingsVersion",
+            ),
+            include_order_version: parse_enum_field(
+                content,
+                "IncludeOrderVersion",
+                "EngineIncludeOrderVersion",
+            ),
+            extra_module_names: parse_string_list(content, "ExtraModuleNames"),
+            path: path.clone(),
+            is_engine,
+        }
+    }
+
+    pub fn to_value(&self, span: Span) -> Value {
+        Value::record(
+            record! {
+                "name" => Value::string(&self.name, span),
+                "type" => self.target_type.as_ref().map(|t| Value::string(t, span)).unwrap_or_default(),
+                "default_build_settings" => self.default_build_settings.as_ref().map(|s| Value::string(s, span)).unwrap_or_default(),
+                "include_order_version" => self.include_order_version.as_ref().map(|v| Value::string(v, span)).unwrap_or_default(),
+                "extra_module_names" => Value::list(self.extra_module_names.iter().map(|m| Value::string(m, span)).collect(), span),
+                "path" => Value::string(self.path.display().to_string(), span),
+                "is_engine" => Value::bool(self.is_engine, span),
+            },
+            span,
+        )
+    }
+}
+
+/**
+ * Returns the targets of the project Source directory and of the engine.
+ * Programs are only listed for source builds, installed builds only contain the engine targets.
+ */
+pub fn find_targets(
+    project_dir: &PathBuf,
+    unreal_engine_path: Option<&PathBuf>,
+) -> Result<Vec<TargetRules>, LabeledError> {
+    let mut targets = vec![];
+    for path in ue_paths::find_files(&project_dir.join("Source"), &[".Target.cs"]) {
+        targets.extend(TargetRules::from_path(&path, false)?);
+    }
+
+    if let Some(unreal_engine_path) = unreal_engine_path {
+        let engine_source = unreal_engine_path.join("Engine/Source");
+        let paths = if ue_engines::is_installed_build(unreal_engine_path) {
+            engine_source
+                .read_dir()
+                .map(|entries| {
+                    entries
+                        .filter_map(|entry| entry.ok())
+                        .map(|entry| entry.path())
+                        .filter(|path| path.to_string_lossy().ends_with(".Target.cs"))
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            ue_paths::find_files(&engine_source, &[".Target.cs"])
+        };
+        for path in paths {
+            targets.extend(TargetRules::from_path(&path, true)?);
+        }
+    }
+    Ok(targets)
+}
+
+// https://dev.epicgames.com/documentation/en-us/unreal-engine/module-properties-in-unreal-engine
+
+pub struct ModuleRules {
+    pub name: String,
+    pub plugin: Option<String>,
+    pub public_dependency_module_names: Vec<String>,
+    pub private_dependency_module_names: Vec<String>,
+    pub dynamically_loaded_module_names: Vec<String>,
+    pub pch_usage: Option<String>,
+    pub private_pch_header_file: Option<String>,
+    pub public_include_paths: Vec<String>,
+    pub private_include_paths: Vec<String>,
+    pub path: PathBuf,
+}
+
+// Get the name of the plugin containing the file, the first parent directory with a .uplugin
+fn get_plugin_name(path: &PathBuf, project_dir: &PathBuf) -> Option<String> {
+    path.ancestors()
+        .skip(1)
+        .take_while(|dir| *dir != project_dir.as_path())
+        .find_map(|dir| {
+            dir.read_dir()
+                .ok()?
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .find(|path| path.extension().is_some_and(|ext| ext == "uplugin"))
+                .map(|path| path.file_stem().unwrap().to_string_lossy().to_string())
+        })
+}
+
+impl ModuleRules {
+    pub fn from_path(path: &PathBuf, project_dir: &PathBuf) -> Result<ModuleRules, LabeledError> {
+        let content = read_rules_file(path)?;
+        Ok(ModuleRules::from_content(
+            &content,
+            path,
+            get_plugin_name(path, project_dir),
+        ))
+    }
+
+    fn from_content(content: &str, path: &PathBuf, plugin: Option<String>) -> ModuleRules {
+        let name = MODULE_CLASS_REGEX
+            .captures(content)
+            .map(|cap| cap[1].to_string())
+            .unwrap_or_else(|| {
+                let file_name = path.file_name().unwrap().to_string_lossy();
+                file_name.trim_end_matches(".Build.cs").to_string()
+            });
+
+        ModuleRules {
+            name,
+            plugin,
+            public_dependency_module_names: parse_string_list(
+                content,
+                "PublicDependencyModuleNames",
+            ),
+            private_dependency_module_names: parse_string_list(
+                content,
+                "PrivateDependencyModuleNames",
+            ),
+            dynamically_loaded_module_names: parse_string_list(
+                content,
+                "DynamicallyLoadedModuleNames",
+            ),
+            pch_usage: parse_enum_field(content, "PCHUsage", "PCHUsageMode"),
+            private_pch_header_file: PCH_HEADER_FILE_REGEX
+                .captures(content)
+                .map(|cap| cap[1].to_string()),
+            public_include_paths: parse_string_list(content, "PublicIncludePaths"),
+            private_include_paths: parse_string_list(content, "PrivateIncludePaths"),
+            path: path.clone(),
+        }
+    }
+
+    pub fn to_value(&self, span: Span) -> Value {
+        let list = |names: &[String]| {
+            Value::list(names.iter().map(|n| Value::string(n, span)).collect(), span)
+        };
+        Value::record(
+            record! {
+                "name" => Value::string(&self.name, span),
+                "plugin" => self.plugin.as_ref().map(|p| Value::string(p, span)).unwrap_or_default(),
+                "public_dependency_module_names" => list(&self.public_dependency_module_names),
+                "private_dependency_module_names" => list(&self.private_dependency_module_names),
+                "dynamically_loaded_module_names" => list(&self.dynamically_loaded_module_names),
+                "pch_usage" => self.pch_usage.as_ref().map(|p| Value::string(p, span)).unwrap_or_default(),
+                "private_pch_header_file" => self.private_pch_header_file.as_ref().map(|p| Value::string(p, span)).unwrap_or_default(),
+                "public_include_paths" => list(&self.public_include_paths),
+                "private_include_paths" => list(&self.private_include_paths),
+                "path" => Value::string(self.path.display().to_string(), span),
+            },
+            span,
+        )
+    }
+
+    /**
+     * Returns the edges (dependency, kind) of the module in the dependency graph
+     */
+    pub fn dependencies(&self) -> Vec<(&str, &str)> {
+        let mut dependencies = vec![];
+        for (names, kind) in [
+            (&self.public_dependency_module_names, "public"),
+            (&self.private_dependency_module_names, "private"),
+            (&self.dynamically_loaded_module_names, "dynamic"),
+        ] {
+            dependencies.extend(names.iter().map(|name| (name.as_str(), kind)));
+        }
+        dependencies
+    }
+}
+
+/**
+ * Returns the modules of the project Source and Plugins directories
+ */
+pub fn find_modules(project_dir: &PathBuf) -> Result<Vec<ModuleRules>, LabeledError> {
+    let mut modules = vec![];
+    for directory in ["Source", "Plugins"] {
+        for path in ue_paths::find_files(&project_dir.join(directory), &[".Build.cs"]) {
+            modules.push(ModuleRules::from_path(&path, project_dir)?);
+        }
+    }
+    Ok(modules)
+}
+
+#[test]
+fn test_parse_target_rules() {
+    let content = strip_comments(
+        r#"using UnrealBuildTool;
+using System.Collections.Generic;
+
+public class VestigeEditorTarget : TargetRules
+{
+	public VestigeEditorTarget(TargetInfo Target) : base(Target)
+	{
+		// Type = TargetType.Game;
+		Type = TargetType.Editor;
+		DefaultBuildSettings = BuildSettingsVersion.V5;
+		IncludeOrderVersion = EngineIncludeOrderVersion.Unreal5_4;
+		/* ExtraModuleNames.Add("VestigeDebug"); */
+		ExtraModuleNames.AddRange(new string[] { "Vestige", "VestigeEditor" });
+	}
+}
+
+public class VestigeServerTarget : TargetRules
+{
+	public VestigeServerTarget(TargetInfo Target) : base(Target)
+	{
+		Type = TargetType.Server;
+		ExtraModuleNames.Add("Vestige");
+	}
+}
+"#,
+    );
+    let path = PathBuf::from("Source/VestigeEditor.Target.cs");
+    let targets = TargetRules::from_content(&content, &path, false);
+
+    assert_eq!(targets.len(), 2);
+    assert_eq!(targets[0].name, "VestigeEditor");
+    assert_eq!(targets[0].target_type.as_deref(), Some("Editor"));
+    assert_eq!(targets[0].default_build_settings.as_deref(), Some("V5"));
+    assert_eq!(
+        targets[0].include_order_version.as_deref(),
+        Some("Unreal5_4")
+    );
+    assert_eq!(targets[0].extra_module_names, ["Vestige", "VestigeEditor"]);
+    assert_eq!(targets[1].name, "VestigeServer");
+    assert_eq!(targets[1].target_type.as_deref(), Some("Server"));
+    assert_eq!(targets[1].default_build_settings, None);
+    assert_eq!(targets[1].extra_module_names, ["Vestige"]);
+
+    // Without a TargetRules class, the target is named after the file
+    let targets = TargetRules::from_content("", &path, true);
+    assert_eq!(targets.len(), 1);
+    assert_eq!(targets[0].name, "VestigeEditor");
+    assert_eq!(targets[0].target_type, None);
+    assert!(targets[0].is_engine);
+}
+
+#[test]
+fn test_strip_comments() {
+    let content = strip_comments(
+        r#"// Copyright Epic Games, Inc. All Rights Reserved.
+PublicIncludePaths.Add("ThirdParty/lib//include"); // The headers
+/* PrivateIncludePaths.Add("Disabled"); */
+PrivateIncludePaths.Add(@"C:\SDK\/*include*/");"#,
+    );
+    assert_eq!(
+        content,
+        r#"
+PublicIncludePaths.Add("ThirdParty/lib//include"); 
+
+PrivateIncludePaths.Add(@"C:\SDK\/*include*/");"#
+    );
+}
+
+#[test]
+fn test_parse_module_rules() {
+    let content = strip_comments(
+        r#"// Copyright Epic Games, Inc. All Rights Reserved.
+
+using System.IO;
+using UnrealBuildTool;
+
+public class Vestige : ModuleRules
+{
+	public Vestige(ReadOnlyTargetRules Target) : base(Target)
+	{
+		PCHUsage = PCHUsageMode.UseExplicitOrSharedPCHs;
+		PrivatePCHHeaderFile = "Private/VestigePCH.h";
+
+		PublicDependencyModuleNames.AddRange(new string[] { "Core", "CoreUObject", "Engine", "InputCore" });
+
+		PrivateDependencyModuleNames.AddRange(
+			new string[]
+			{
+				"Slate",
+				"SlateCore",
+				// "OnlineSubsystem",
+			}
+		);
+
+		if (Target.bBuildEditor)
+		{
+			PrivateDependencyModuleNames.Add("UnrealEd");
+		}
+
+		DynamicallyLoadedModuleNames.Add("OnlineSubsystemSteam");
+
+		PublicIncludePaths.Add(Path.Combine(ModuleDirectory, "Public"));
+		PublicIncludePaths.Add("Vestige/ThirdParty/include");
+		PrivateIncludePaths.AddRange(new string[] { Path.Combine(ModuleDirectory, "Private"), "Vestige/Private" });
+	}
+}
+"#,
+    );
+    let module = ModuleRules::from_content(
+        &content,
+        &PathBuf::from("Source/Vestige/Vestige.Build.cs"),
+        None,
+    );
+
+    assert_eq!(module.name, "Vestige");
+    assert_eq!(
+        module.public_dependency_module_names,
+        ["Core", "CoreUObject", "Engine", "InputCore"]
+    );
+    assert_eq!(
+        module.private_dependency_module_names,
+        ["Slate", "SlateCore", "UnrealEd"]
+    );
+    assert_eq!(
+        module.dynamically_loaded_module_names,
+        ["OnlineSubsystemSteam"]
+    );
+    assert_eq!(module.pch_usage.as_deref(), Some("UseExplicitOrSharedPCHs"));
+    assert_eq!(
+        module.private_pch_header_file.as_deref(),
+        Some("Private/VestigePCH.h")
+    );
+    assert_eq!(module.public_include_paths, ["Vestige/ThirdParty/include"]);
+    assert_eq!(module.private_include_paths, ["Vestige/Private"]);
+    assert_eq!(
+        module.dependencies(),
+        [
+            ("Core", "public"),
+            ("CoreUObject", "public"),
+            ("Engine", "public"),
+            ("InputCore", "public"),
+            ("Slate", "private"),
+            ("SlateCore", "private"),
+            ("UnrealEd", "private"),
+            ("OnlineSubsystemSteam", "dynamic"),
+        ]
+    );
+}
@@ -0,0 +1,746 @@
+use nu_protocol::{LabeledError, Span, Value, record};
+use uasset::{AssetHeader, NameReference, ObjectImport, ObjectImportOuter};
+
+// Tables of the package that are not read by uasset-rs
+// https://github.com/EpicGames/UnrealEngine/blob/release/Engine/Source/Runtime/CoreUObject/Private/UObject/ObjectResource.cpp
+
+// EUnrealEngineObjectUE4Version
+const VER_UE4_LOAD_FOR_EDITOR_GAME: i32 = 365;
+const VER_UE4_ADDED_SOFT_OBJECT_PATH: i32 = 514;
+const VER_UE4_COOKED_ASSETS_IN_EDITOR_SUPPORT: i32 = 485;
+const VER_UE4_PRELOAD_DEPENDENCIES_IN_COOKED_EXPORTS: i32 = 507;
+const VER_UE4_TEMPLATE_INDEX_IN_COOKED_EXPORTS: i32 = 508;
+const VER_UE4_64BIT_EXPORTMAP_SERIALSIZES: i32 = 511;
+const VER_UE4_ASSETREGISTRY_DEPENDENCYFLAGS: i32 = 521;
+
+// EUnrealEngineObjectUE5Version
+const VER_UE5_OPTIONAL_RESOURCES: i32 = 1003;
+const VER_UE5_REMOVE_OBJECT_EXPORT_PACKAGE_GUID: i32 = 1005;
+const VER_UE5_TRACK_OBJECT_EXPORT_IS_INHERITED: i32 = 1006;
+const VER_UE5_ADD_SOFTOBJECTPATH_LIST: i32 = 1008;
+const VER_UE5_SCRIPT_SERIALIZATION_OFFSET: i32 = 1010;
+
+// EPackageFlags
+const PKG_FILTER_EDITOR_ONLY: u32 = 0x80000000;
+
+/**
+ * Little endian reader of the package bytes, aware of the package versions
+ */
+pub struct PackageReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    pub file_version: i32,
+    pub file_version_ue5: i32,
+}
+
+impl<'a> PackageReader<'a> {
+    pub fn new<R: std::io::Read>(bytes: &'a [u8], header: &AssetHeader<R>) -> PackageReader<'a> {
+        PackageReader {
+            bytes,
+            position: 0,
+            file_version: header.archive.file_version as i32,
+            file_version_ue5: header.archive.file_version_ue5.unwrap_or(0) as i32,
+        }
+    }
+
+    pub fn seek(&mut self, offset: i64) -> Result<(), LabeledError> {
+        if offset < 0 || offset as usize > self.bytes.len() {
+            return Err(LabeledError::new(format!(
+                "Invalid offset {} in a package of {} bytes",
+                offset,
+                self.bytes.len()
+            )));
+        }
+        self.position = offset as usize;
+        Ok(())
+    }
+
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], LabeledError> {
+        Ok(self.read_slice(N)?.try_into().unwrap())
+    }
+
+    pub fn read_i32(&mut self) -> Result<i32, LabeledError> {
+        Ok(i32::from_le_bytes(self.read_bytes()?))
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, LabeledError> {
+        Ok(u32::from_le_bytes(self.read_bytes()?))
+    }
+
+    pub fn read_i64(&mut self) -> Result<i64, LabeledError> {
+        Ok(i64::from_le_bytes(self.read_bytes()?))
+    }
+
+    // UBOOL are serialized on 4 bytes
+    pub fn read_bool(&mut self) -> Result<bool, LabeledError> {
+        Ok(self.read_u32()? != 0)
+    }
+
+    pub fn read_name(&mut self) -> Result<PackageName, LabeledError> {
+        Ok(PackageName {
+            index: self.read_i32()?,
+            number: self.read_i32()?,
+        })
+    }
+
+    // FString: the length includes the null terminator, negative for UTF-16
+    pub fn read_fstring(&mut self) -> Result<String, LabeledError> {
+        let length = self.read_i32()?;
+        let string = if length >= 0 {
+            let bytes = self.read_slice(length as usize)?;
+            String::from_utf8_lossy(bytes).to_string()
+        } else {
+            let bytes = self.read_slice(length.unsigned_abs() as usize * 2)?;
+            let units: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        };
+        Ok(string.trim_end_matches('\0').to_string())
+    }
+
+    fn read_slice(&mut self, count: usize) -> Result<&'a [u8], LabeledError> {
+        let bytes =
+            self.bytes
+                .get(self.position..self.position + count)
+                .ok_or(LabeledError::new(format!(
+                    "Unexpected end of package at offset {}",
+                    self.position
+                )))?;
+        self.position += count;
+        Ok(bytes)
+    }
+
+    pub fn skip(&mut self, count: usize) -> Result<(), LabeledError> {
+        self.seek((self.position + count) as i64)
+    }
+}
+
+/**
+ * FName serialized as an index in the name map and a number
+ */
+#[derive(Clone, Copy)]
+pub struct PackageName {
+    pub index: i32,
+    pub number: i32,
+}
+
+impl PackageName {
+    // The number is the suffix + 1, 0 means no suffix
+    pub fn resolve(&self, names: &[String]) -> String {
+        let name = names
+            .get(self.index as usize)
+            .cloned()
+            .unwrap_or_else(|| format!("<invalid name {}>", self.index));
+        if self.number > 0 {
+            format!("{}_{}", name, self.number - 1)
+        } else {
+            name
+        }
+    }
+}
+
+impl From<NameReference> for PackageName {
+    fn from(name: NameReference) -> Self {
+        PackageName {
+            index: name.index as i32,
+            number: name.number.map(|n| n.get() as i32).unwrap_or(0),
+        }
+    }
+}
+
+pub struct ObjectExport {
+    pub class_index: i32,
+    pub super_index: i32,
+    pub template_index: i32,
+    pub outer_index: i32,
+    pub object_name: PackageName,
+    pub object_flags: u32,
+    pub serial_size: i64,
+    pub serial_offset: i64,
+    pub forced_export: bool,
+    pub not_for_client: bool,
+    pub not_for_server: bool,
+    pub is_inherited_instance: bool,
+    pub package_flags: u32,
+    pub not_always_loaded_for_editor_game: bool,
+    pub is_asset: bool,
+    pub generate_public_hash: bool,
+}
+
+fn read_export(reader: &mut PackageReader) -> Result<ObjectExport, LabeledError> {
+    let class_index = reader.read_i32()?;
+    let super_index = reader.read_i32()?;
+    let template_index = if reader.file_version >= VER_UE4_TEMPLATE_INDEX_IN_COOKED_EXPORTS {
+        reader.read_i32()?
+    } else {
+        0
+    };
+    let outer_index = reader.read_i32()?;
+    let object_name = reader.read_name()?;
+    let object_flags = reader.read_u32()?;
+    let (serial_size, serial_offset) = if reader.file_version >= VER_UE4_64BIT_EXPORTMAP_SERIALSIZES
+    {
+        (reader.read_i64()?, reader.read_i64()?)
+    } else {
+        (reader.read_i32()? as i64, reader.read_i32()? as i64)
+    };
+    let forced_export = reader.read_bool()?;
+    let not_for_client = reader.read_bool()?;
+    let not_for_server = reader.read_bool()?;
+    if reader.file_version_ue5 < VER_UE5_REMOVE_OBJECT_EXPORT_PACKAGE_GUID {
+        // PackageGuid
+        reader.skip(16)?;
+    }
+    let is_inherited_instance = reader.file_version_ue5 >= VER_UE5_TRACK_OBJECT_EXPORT_IS_INHERITED
+        && reader.read_bool()?;
+    let package_flags = reader.read_u32()?;
+    let not_always_loaded_for_editor_game =
+        reader.file_version >= VER_UE4_LOAD_FOR_EDITOR_GAME && reader.read_bool()?;
+    let is_asset =
+        reader.file_version >= VER_UE4_COOKED_ASSETS_IN_EDITOR_SUPPORT && reader.read_bool()?;
+    let generate_public_hash =
+        reader.file_version_ue5 >= VER_UE5_OPTIONAL_RESOURCES && reader.read_bool()?;
+    if reader.file_version >= VER_UE4_PRELOAD_DEPENDENCIES_IN_COOKED_EXPORTS {
+        // FirstExportDependency and the 4 dependency counts
+        reader.skip(5 * 4)?;
+    }
+    if reader.file_version_ue5 >= VER_UE5_SCRIPT_SERIALIZATION_OFFSET {
+        // ScriptSerializationStartOffset and ScriptSerializationEndOffset
+        reader.skip(2 * 8)?;
+    }
+
+    Ok(ObjectExport {
+        class_index,
+        super_index,
+        template_index,
+        outer_index,
+        object_name,
+        object_flags,
+        serial_size,
+        serial_offset,
+        forced_export,
+        not_for_client,
+        not_for_server,
+        is_inherited_instance,
+        package_flags,
+        not_always_loaded_for_editor_game,
+        is_asset,
+        generate_public_hash,
+    })
+}
+
+/**
+ * Read the export table at export_offset
+ */
+pub fn read_exports<R: std::io::Read>(
+    bytes: &[u8],
+    header: &AssetHeader<R>,
+) -> Result<Vec<ObjectExport>, LabeledError> {
+    let mut reader = PackageReader::new(bytes, header);
+    reader.seek(header.export_offset as i64)?;
+    (0..header.export_count)
+        .map(|_| read_export(&mut reader))
+        .collect()
+}
+
+/**
+ * Get the object name of an import (negative index) or an export (positive index), None for 0
+ */
+pub fn resolve_object_name<R: std::io::Read>(
+    index: i32,
+    header: &AssetHeader<R>,
+    exports: &[ObjectExport],
+) -> Option<String> {
+    if index < 0 {
+        let import = header.imports.get((-index - 1) as usize)?;
+        Some(PackageName::from(import.object_name).resolve(&header.names))
+    } else if index > 0 {
+        let export = exports.get((index - 1) as usize)?;
+        Some(export.object_name.resolve(&header.names))
+    } else {
+        None
+    }
+}
+
+/**
+ * Get the package index of the outer of an import: 0 for root, negative for imports, positive for exports
+ */
+pub fn import_outer_index(import: &ObjectImport) -> i32 {
+    match import.outer() {
+        // Reverse https://github.com/jorgenpt/uasset-rs/blob/8b6ecff0c0b19c2a7e0556375742ed90095ff881/src/lib.rs#L171-L178
+        // Same as https://github.com/EpicGames/UnrealEngine/blob/2d53fcab0066b1f16dd956b227720841cad0f6f7/Engine/Source/Runtime/CoreUObject/Public/UObject/ObjectResource.h#L90-L101
+        ObjectImportOuter::Root => 0,
+        ObjectImportOuter::Import { import_index } => -(import_index as i32) - 1,
+        ObjectImportOuter::Export { export_index } => (export_index as i32) + 1,
+    }
+}
+
+// Guard against corrupted packages with cyclic outers
+const MAX_OUTER_DEPTH: usize = 64;
+
+/**
+ * Resolve the path of imports and exports by walking their outers
+ */
+pub struct ObjectResolver<'a, R: std::io::Read> {
+    pub header: &'a AssetHeader<R>,
+    pub exports: &'a [ObjectExport],
+    // Name of the package containing the exports, e.g. /Game/Meshes/SM_Rock
+    pub package_name: Option<&'a str>,
+}
+
+impl<'a, R: std::io::Read> ObjectResolver<'a, R> {
+    /**
+     * Get the object path, e.g. /Game/Meshes/SM_Rock.SM_Rock or /Game/Maps/Map.Map:PersistentLevel.Actor
+     */
+    pub fn object_path(&self, index: i32) -> Option<String> {
+        let mut names = vec![];
+        let mut index = index;
+        while index != 0 {
+            if names.len() >= MAX_OUTER_DEPTH {
+                return None;
+            }
+            if index < 0 {
+                let import = self.header.imports.get((-index - 1) as usize)?;
+                names.push(PackageName::from(import.object_name).resolve(&self.header.names));
+                index = import_outer_index(import);
+            } else {
+                let export = self.exports.get((index - 1) as usize)?;
+                names.push(export.object_name.resolve(&self.header.names));
+                index = export.outer_index;
+                if index == 0 {
+                    // The outer of top level exports is the package itself
+                    names.push(self.package_name.unwrap_or_default().to_string());
+                }
+            }
+        }
+        if names.is_empty() {
+            return None;
+        }
+        names.reverse();
+
+        // Objects in a package use '.', sub objects of an asset use ':'
+        let mut path = names[0].clone();
+        for (depth, name) in names.iter().enumerate().skip(1) {
+            if depth > 1 || !path.is_empty() {
+                path.push(if depth == 2 { ':' } else { '.' });
+            }
+            path.push_str(name);
+        }
+        Some(path)
+    }
+
+    /**
+     * Get the class path, e.g. /Script/Engine.StaticMesh
+     */
+    pub fn class_path(&self, index: i32) -> Option<String> {
+        if index < 0 {
+            let import = self.header.imports.get((-index - 1) as usize)?;
+            Some(format!(
+                "{}.{}",
+                PackageName::from(import.class_package).resolve(&self.header.names),
+                PackageName::from(import.class_name).resolve(&self.header.names)
+            ))
+        } else if index > 0 {
+            let export = self.exports.get((index - 1) as usize)?;
+            match export.class_index {
+                // Exports without class are classes
+                0 => Some("/Script/CoreUObject.Class".to_string()),
+                class_index => self.object_path(class_index),
+            }
+        } else {
+            None
+        }
+    }
+
+    /**
+     * Get the full path, e.g. /Script/Engine.StaticMesh'/Game/Meshes/SM_Rock.SM_Rock'
+     */
+    pub fn full_path(&self, index: i32) -> Option<String> {
+        Some(format!(
+            "{}'{}'",
+            self.class_path(index)?,
+            self.object_path(index)?
+        ))
+    }
+}
+
+/**
+ * Get the packages imported by the package, the imports of class Package at the root
+ */
+pub fn read_package_imports<R: std::io::Read>(header: &AssetHeader<R>) -> Vec<String> {
+    header
+        .imports
+        .iter()
+        .filter(|import| {
+            import_outer_index(import) == 0
+                && PackageName::from(import.class_name).resolve(&header.names) == "Package"
+        })
+        .map(|import| PackageName::from(import.object_name).resolve(&header.names))
+        .collect()
+}
+
+/**
+ * Read the soft package references at soft_package_references_offset
+ */
+pub fn read_soft_package_references<R: std::io::Read>(
+    bytes: &[u8],
+    header: &AssetHeader<R>,
+) -> Result<Vec<String>, LabeledError> {
+    if header.soft_package_references_count as i64 <= 0 {
+        return Ok(vec![]);
+    }
+    let mut reader = PackageReader::new(bytes, header);
+    reader.seek(header.soft_package_references_offset as i64)?;
+    (0..header.soft_package_references_count)
+        .map(|_| {
+            if reader.file_version < VER_UE4_ADDED_SOFT_OBJECT_PATH {
+                reader.read_fstring()
+            } else {
+                Ok(reader.read_name()?.resolve(&header.names))
+            }
+        })
+        .collect()
+}
+
+/**
+ * FSoftObjectPath, e.g. /Game/Maps/Map.Map:PersistentLevel.Actor is {package: /Game/Maps/Map, asset: Map, sub_path: PersistentLevel.Actor}
+ */
+pub struct SoftObjectPath {
+    pub package: String,
+    pub asset: String,
+    pub sub_path: String,
+}
+
+impl SoftObjectPath {
+    pub fn to_value(&self, span: Span) -> Value {
+        Value::record(
+            record! {
+                "package" => Value::string(&self.package, span),
+                "asset" => Value::string(&self.asset, span),
+                "sub_path" => Value::string(&self.sub_path, span),
+            },
+            span,
+        )
+    }
+}
+
+/**
+ * Read the soft object paths at soft_object_paths_offset, the table is only saved since UE 5.1
+ */
+pub fn read_soft_object_paths<R: std::io::Read>(
+    bytes: &[u8],
+    header: &AssetHeader<R>,
+) -> Result<Vec<SoftObjectPath>, LabeledError> {
+    let mut reader = PackageReader::new(bytes, header);
+    if reader.file_version_ue5 < VER_UE5_ADD_SOFTOBJECTPATH_LIST
+        || header.soft_object_paths_count as i64 <= 0
+    {
+        return Ok(vec![]);
+    }
+    reader.seek(header.soft_object_paths_offset as i64)?;
+    (0..header.soft_object_paths_count)
+        .map(|_| {
+            // FTopLevelAssetPath (package and asset FNames) and the sub path FString
+            Ok(SoftObjectPath {
+                package: reader.read_name()?.resolve(&header.names),
+                asset: reader.read_name()?.resolve(&header.names),
+                sub_path: reader.read_fstring()?,
+            })
+        })
+        .collect()
+}
+
+/**
+ * Get the packages referenced by the soft package references and the soft object paths
+ */
+pub fn read_soft_package_dependencies<R: std::io::Read>(
+    bytes: &[u8],
+    header: &AssetHeader<R>,
+) -> Result<Vec<String>, LabeledError> {
+    let mut packages = read_soft_package_references(bytes, header)?;
+    packages.extend(
+        read_soft_object_paths(bytes, header)?
+            .into_iter()
+            .map(|path| path.package)
+            .filter(|package| !package.is_empty()),
+    );
+    packages.sort();
+    packages.dedup();
+    Ok(packages)
+}
+
+/**
+ * Asset of the package as saved for the asset registry, with the tags used to filter assets without loading them
+ */
+pub struct AssetRegistryEntry {
+    pub object_path: String,
+    pub class: String,
+    pub tags: Vec<(String, String)>,
+}
+
+impl AssetRegistryEntry {
+    pub fn to_value(&self, span: Span) -> Value {
+        Value::record(
+            record! {
+                "object_path" => Value::string(&self.object_path, span),
+                "class" => Value::string(&self.class, span),
+                "tags" => Value::record(
+                    self.tags
+                        .iter()
+                        .map(|(key, value)| (key.clone(), Value::string(value, span)))
+                        .collect(),
+                    span,
+                ),
+            },
+            span,
+        )
+    }
+}
+
+/**
+ * Read the asset registry data at asset_registry_data_offset
+ */
+pub fn read_asset_registry<R: std::io::Read>(
+    bytes: &[u8],
+    header: &AssetHeader<R>,
+    package_name: Option<&str>,
+) -> Result<Vec<AssetRegistryEntry>, LabeledError> {
+    if header.asset_registry_data_offset as i64 <= 0 {
+        return Ok(vec![]);
+    }
+    let mut reader = PackageReader::new(bytes, header);
+    reader.seek(header.asset_registry_data_offset as i64)?;
+    let is_filter_editor_only = header.package_flags & PKG_FILTER_EDITOR_ONLY != 0;
+    if !is_filter_editor_only && reader.file_version >= VER_UE4_ASSETREGISTRY_DEPENDENCYFLAGS {
+        // DependencyDataOffset
+        reader.skip(8)?;
+    }
+
+    let object_count = reader.read_i32()?;
+    (0..object_count.max(0))
+        .map(|_| {
+            // UE5 saves the object path without the package name
+            let object_path = match (package_name, reader.read_fstring()?) {
+                (Some(package_name), path) if !path.starts_with('/') && !path.contains('.') => {
+                    format!("{}.{}", package_name, path)
+                }
+                (_, path) => path,
+            };
+            let class = reader.read_fstring()?;
+            let tag_count = reader.read_i32()?;
+            let tags = (0..tag_count.max(0))
+                .map(|_| Ok((reader.read_fstring()?, reader.read_fstring()?)))
+                .collect::<Result<Vec<_>, LabeledError>>()?;
+            Ok(AssetRegistryEntry {
+                object_path,
+                class,
+                tags,
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_read_exports_ue5() {
+    let bytes = include_bytes!("../../tests/fixtures/Content/Meshes/SM_Rock.uasset");
+    let header = AssetHeader::new(std::io::Cursor::new(bytes.as_slice())).unwrap();
+    let exports = read_exports(bytes, &header).unwrap();
+
+    assert_eq!(header.archive.file_version_ue5, Some(1012));
+    assert_eq!(exports.len(), 3);
+    assert_eq!(exports[0].object_name.resolve(&header.names), "SM_Rock");
+    assert_eq!(exports[0].class_index, -2);
+    assert_eq!(exports[0].template_index, -6);
+    assert_eq!(exports[0].outer_index, 0);
+    assert_eq!(exports[0].object_flags, 0xB);
+    assert_eq!(exports[0].serial_size, 16);
+    assert_eq!(exports[0].serial_offset, header.total_header_size as i64);
+    assert!(exports[0].is_asset);
+    assert!(!exports[0].is_inherited_instance);
+    assert!(!exports[0].generate_public_hash);
+    // The suffix of a name is saved as its number + 1
+    assert_eq!(exports[1].object_name.number, 1);
+    assert_eq!(exports[1].object_name.resolve(&header.names), "BodySetup_0");
+    assert_eq!(exports[1].outer_index, 1);
+    assert_eq!(
+        exports[1].serial_offset,
+        header.total_header_size as i64 + 16
+    );
+    assert!(!exports[1].is_asset);
+    // Names that are not ASCII are saved in UTF-16
+    assert_eq!(exports[2].object_name.resolve(&header.names), "Géométrie");
+    assert_eq!(exports[2].serial_size, 4);
+}
+
+#[test]
+fn test_read_exports_ue5_0() {
+    // Saved before the removal of the PackageGuid and the IsInheritedInstance flag
+    let bytes = include_bytes!("../../tests/fixtures/Content/Blueprints/BP_Door.uasset");
+    let header = AssetHeader::new(std::io::Cursor::new(bytes.as_slice())).unwrap();
+    let exports = read_exports(bytes, &header).unwrap();
+
+    assert_eq!(header.archive.file_version_ue5, Some(1004));
+    assert_eq!(exports.len(), 2);
+    assert_eq!(exports[0].object_name.resolve(&header.names), "BP_Door_C");
+    assert!(exports[0].is_asset);
+    assert_eq!(
+        exports[1].object_name.resolve(&header.names),
+        "Default__BP_Door_C"
+    );
+    assert_eq!(exports[1].class_index, 1);
+    assert_eq!(exports[1].template_index, -3);
+    assert_eq!(
+        exports[1].serial_offset,
+        header.total_header_size as i64 + 4
+    );
+}
+
+#[test]
+fn test_read_exports_ue4() {
+    let bytes = include_bytes!("../../tests/fixtures/Content/Legacy/SM_Crate.uasset");
+    let header = AssetHeader::new(std::io::Cursor::new(bytes.as_slice())).unwrap();
+    let exports = read_exports(bytes, &header).unwrap();
+
+    assert_eq!(header.archive.file_version_ue5, None);
+    assert_eq!(exports.len(), 2);
+    assert_eq!(exports[0].object_name.resolve(&header.names), "SM_Crate");
+    assert_eq!(exports[0].serial_size, 8);
+    assert!(exports[0].is_asset);
+    assert_eq!(exports[1].object_name.resolve(&header.names), "BodySetup_0");
+    assert_eq!(exports[1].outer_index, 1);
+    assert_eq!(
+        exports[1].serial_offset,
+        header.total_header_size as i64 + 8
+    );
+}
+
+#[test]
+fn test_object_resolver() {
+    let bytes = include_bytes!("../../tests/fixtures/Content/Meshes/SM_Rock.uasset");
+    let header = AssetHeader::new(std::io::Cursor::new(bytes.as_slice())).unwrap();
+    let exports = read_exports(bytes, &header).unwrap();
+    let resolver = ObjectResolver {
+        header: &header,
+        exports: &exports,
+        package_name: Some("/Game/Meshes/SM_Rock"),
+    };
+
+    assert_eq!(
+        resolver.full_path(1).as_deref(),
+        Some("/Script/Engine.StaticMesh'/Game/Meshes/SM_Rock.SM_Rock'")
+    );
+    assert_eq!(
+        resolver.object_path(2).as_deref(),
+        Some("/Game/Meshes/SM_Rock.SM_Rock:BodySetup_0")
+    );
+    assert_eq!(
+        resolver.full_path(-5).as_deref(),
+        Some("/Script/Engine.Material'/Game/Materials/M_Rock.M_Rock'")
+    );
+    assert_eq!(
+        resolver.full_path(-2).as_deref(),
+        Some("/Script/CoreUObject.Class'/Script/Engine.StaticMesh'")
+    );
+    assert_eq!(resolver.object_path(0), None);
+    assert_eq!(resolver.object_path(4), None);
+    assert_eq!(
+        resolve_object_name(-6, &header, &exports).as_deref(),
+        Some("Default__StaticMesh")
+    );
+    assert_eq!(
+        read_package_imports(&header),
+        vec!["/Script/Engine", "/Game/Materials/M_Rock"]
+    );
+
+    // The class of an export can be another export of the package
+    let bytes = include_bytes!("../../tests/fixtures/Content/Blueprints/BP_Door.uasset");
+    let header = AssetHeader::new(std::io::Cursor::new(bytes.as_slice())).unwrap();
+    let exports = read_exports(bytes, &header).unwrap();
+    let resolver = ObjectResolver {
+        header: &header,
+        exports: &exports,
+        package_name: Some("/Game/Blueprints/BP_Door"),
+    };
+    assert_eq!(
+        resolver.full_path(2).as_deref(),
+        Some("/Game/Blueprints/BP_Door.BP_Door_C'/Game/Blueprints/BP_Door.Default__BP_Door_C'")
+    );
+    assert_eq!(
+        resolver.class_path(1).as_deref(),
+        Some("/Script/Engine.BlueprintGeneratedClass")
+    );
+}
+
+#[test]
+fn test_read_soft_references() {
+    // Since UE 5.1 the soft object paths are listed in the header
+    let bytes = include_bytes!("../../tests/fixtures/Content/Meshes/SM_Rock.uasset");
+    let header = AssetHeader::new(std::io::Cursor::new(bytes.as_slice())).unwrap();
+
+    assert_eq!(
+        read_soft_package_references(bytes, &header).unwrap(),
+        vec!["/Game/Textures/T_Rock"]
+    );
+    let soft_object_paths = read_soft_object_paths(bytes, &header).unwrap();
+    assert_eq!(soft_object_paths.len(), 2);
+    assert_eq!(soft_object_paths[0].package, "/Game/Maps/Main");
+    assert_eq!(soft_object_paths[0].asset, "Main");
+    // FString that are not ASCII are saved in UTF-16
+    assert_eq!(
+        soft_object_paths[0].sub_path,
+        "PersistentLevel.Lumière_Rocher"
+    );
+    assert_eq!(soft_object_paths[1].asset, "T_Rock");
+    assert_eq!(soft_object_paths[1].sub_path, "");
+    assert_eq!(
+        read_soft_package_dependencies(bytes, &header).unwrap(),
+        vec!["/Game/Maps/Main", "/Game/Textures/T_Rock"]
+    );
+
+    // Before UE 5.1 only the soft package references are saved
+    let bytes = include_bytes!("../../tests/fixtures/Content/Blueprints/BP_Door.uasset");
+    let header = AssetHeader::new(std::io::Cursor::new(bytes.as_slice())).unwrap();
+
+    assert!(read_soft_object_paths(bytes, &header).unwrap().is_empty());
+    assert_eq!(
+        read_soft_package_dependencies(bytes, &header).unwrap(),
+        vec!["/Game/Sounds/S_Door"]
+    );
+}
+
+#[test]
+fn test_read_asset_registry() {
+    let bytes = include_bytes!("../../tests/fixtures/Content/Meshes/SM_Rock.uasset");
+    let header = AssetHeader::new(std::io::Cursor::new(bytes.as_slice())).unwrap();
+    let entries = read_asset_registry(bytes, &header, Some("/Game/Meshes/SM_Rock")).unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].object_path, "/Game/Meshes/SM_Rock.SM_Rock");
+    assert_eq!(entries[0].class, "/Script/Engine.StaticMesh");
+    assert_eq!(
+        entries[0].tags[0],
+        ("NumTriangles".to_string(), "120".to_string())
+    );
+    assert_eq!(
+        entries[0].tags[1].1,
+        r#"[{ "RelativeFilename" : "Rocher_é.fbx" }]"#
+    );
+
+    // Editor only data is filtered out of cooked packages, including the DependencyDataOffset
+    let bytes = include_bytes!("../../tests/fixtures/Content/Blueprints/BP_Door.uasset");
+    let header = AssetHeader::new(std::io::Cursor::new(bytes.as_slice())).unwrap();
+    let entries = read_asset_registry(bytes, &header, Some("/Game/Blueprints/BP_Door")).unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].object_path, "/Game/Blueprints/BP_Door.BP_Door_C");
+    assert_eq!(entries[0].class, "/Script/Engine.BlueprintGeneratedClass");
+    assert_eq!(
+        entries[0].tags,
+        vec![(
+            "ParentClass".to_string(),
+            "/Script/CoreUObject.Class'/Script/Engine.Actor'".to_string()
+        )]
+    );
+}
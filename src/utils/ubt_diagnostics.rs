@@ -0,0 +1,182 @@
+use std::sync::LazyLock;
+
+use nu_protocol::{Span, Value, record};
+use regex::{Captures, Regex};
+
+// MSVC, UnrealHeaderTool and C# rules: File.cpp(42,13): error C2065: message
+static MSVC_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(?P<file>.+?)\((?P<line>\d+)(?:,(?P<column>\d+))?\)\s*:\s*(?i:(?P<severity>fatal error|error|warning|note))(?:\s+(?P<code>[A-Z]+\d+))?\s*:\s*(?P<message>.*)$").unwrap()
+});
+// Clang: File.cpp:42:13: error: message [-Wflag]
+static CLANG_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(?P<file>.+?):(?P<line>\d+):(?:(?P<column>\d+):)?\s*(?P<severity>fatal error|error|warning|note):\s*(?P<message>.*?)(?:\s+\[(?P<code>-W[^\]]+)\])?$").unwrap()
+});
+// MSVC linker: Module.Vestige.cpp.obj : error LNK2019: message
+static LINKER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(?P<file>\S+?)\s+:\s+(?P<severity>fatal error|error|warning)\s+(?P<code>LNK\d+):\s*(?P<message>.*)$").unwrap()
+});
+// Unreal logs and AutomationTool: [2024.01.01-12.00.00:000][  0]LogCook: Error: message
+static LOG_ERROR_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(?:\[[^\]]*\])*\s*(?:\w+:\s*)?(?:Fatal error|Error|ERROR):").unwrap()
+});
+// Unity and object files are named after their module: Module.Vestige.cpp.obj
+static MODULE_FILE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:^|[\\/])Module\.(?P<module>\w+)\.").unwrap());
+
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    pub file: String,
+    pub line: Option<i64>,
+    pub column: Option<i64>,
+    pub severity: String,
+    pub code: Option<String>,
+    pub message: String,
+    pub module: Option<String>,
+}
+
+// Get the module from the directory following the last Source directory
+fn get_module(file: &str) -> Option<String> {
+    if let Some(cap) = MODULE_FILE_REGEX.captures(file) {
+        return Some(cap["module"].to_string());
+    }
+    let components: Vec<&str> = file.split(['/', '\\']).collect();
+    let source_index = components.iter().rposition(|c| *c == "Source")?;
+    // The file must be inside the module directory
+    if source_index + 2 >= components.len() {
+        return None;
+    }
+    Some(components[source_index + 1].to_string())
+}
+
+fn diagnostic_from_captures(cap: Captures) -> Diagnostic {
+    let file = cap["file"].trim().to_string();
+    let severity = match cap["severity"].to_lowercase().as_str() {
+        "fatal error" => "error".to_string(),
+        severity => severity.to_string(),
+    };
+    Diagnostic {
+        module: get_module(&file),
+        file,
+        line: cap.name("line").and_then(|m| m.as_str().parse().ok()),
+        column: cap.name("column").and_then(|m| m.as_str().parse().ok()),
+        severity,
+        code: cap.name("code").map(|m| m.as_str().to_string()),
+        message: cap["message"].trim().to_string(),
+    }
+}
+
+/**
+ * Parse a line of UnrealBuildTool output, returns None if it is not a diagnostic
+ */
+pub fn parse_diagnostic(line: &str) -> Option<Diagnostic> {
+    MSVC_REGEX
+        .captures(line)
+        .or_else(|| LINKER_REGEX.captures(line))
+        .or_else(|| CLANG_REGEX.captures(line))
+        .map(diagnostic_from_captures)
+}
+
+/**
+ * Returns true if the line is an error of the compiler, the linker or an Unreal log
+ */
+pub fn is_error_line(line: &str) -> bool {
+    parse_diagnostic(line).is_some_and(|d| d.severity == "error") || LOG_ERROR_REGEX.is_match(line)
+}
+
+impl Diagnostic {
+    pub fn to_value(&self, span: Span) -> Value {
+        Value::record(
+            record! {
+                "file" => Value::string(&self.file, span),
+                "line" => self.line.map(|l| Value::int(l, span)).unwrap_or_default(),
+                "column" => self.column.map(|c| Value::int(c, span)).unwrap_or_default(),
+                "severity" => Value::string(&self.severity, span),
+                "code" => self.code.as_ref().map(|c| Value::string(c, span)).unwrap_or_default(),
+                "message" => Value::string(&self.message, span),
+                "module" => self.module.as_ref().map(|m| Value::string(m, span)).unwrap_or_default(),
+            },
+            span,
+        )
+    }
+}
+
+#[test]
+fn test_parse_msvc_log() {
+    let diagnostics: Vec<Diagnostic> = include_str!("../../tests/fixtures/ubt_msvc.log")
+        .lines()
+        .filter_map(parse_diagnostic)
+        .collect();
+
+    assert_eq!(diagnostics.len(), 5);
+    assert_eq!(
+        diagnostics[0],
+        Diagnostic {
+            file: r"C:\Projects\Vestige\Source\Vestige\Private\VestigeCharacter.cpp".to_string(),
+            line: Some(42),
+            column: None,
+            severity: "error".to_string(),
+            code: Some("C2065".to_string()),
+            message: "'Speed': undeclared identifier".to_string(),
+            module: Some("Vestige".to_string()),
+        }
+    );
+    assert_eq!(diagnostics[1].column, Some(13));
+    assert_eq!(diagnostics[1].severity, "warning");
+    assert_eq!(diagnostics[1].code.as_deref(), Some("C4996"));
+    assert_eq!(diagnostics[2].severity, "error");
+    assert_eq!(diagnostics[2].code.as_deref(), Some("C1083"));
+    assert_eq!(diagnostics[2].module.as_deref(), Some("InventoryRuntime"));
+    assert_eq!(diagnostics[3].file, "Module.Vestige.cpp.obj");
+    assert_eq!(diagnostics[3].line, None);
+    assert_eq!(diagnostics[3].code.as_deref(), Some("LNK2019"));
+    assert_eq!(diagnostics[3].module.as_deref(), Some("Vestige"));
+    assert_eq!(diagnostics[4].code.as_deref(), Some("CS1002"));
+    assert_eq!(diagnostics[4].module.as_deref(), Some("Vestige"));
+}
+
+#[test]
+fn test_parse_clang_log() {
+    let diagnostics: Vec<Diagnostic> = include_str!("../../tests/fixtures/ubt_clang.log")
+        .lines()
+        .filter_map(parse_diagnostic)
+        .collect();
+
+    assert_eq!(diagnostics.len(), 4);
+    assert_eq!(
+        diagnostics[0],
+        Diagnostic {
+            file: "/home/dev/Projects/Vestige/Source/Vestige/Private/VestigeGameMode.cpp"
+                .to_string(),
+            line: Some(25),
+            column: Some(9),
+            severity: "error".to_string(),
+            code: None,
+            message: "use of undeclared identifier 'PlayerCount'".to_string(),
+            module: Some("Vestige".to_string()),
+        }
+    );
+    assert_eq!(diagnostics[1].severity, "warning");
+    assert_eq!(diagnostics[1].code.as_deref(), Some("-Wunused-variable"));
+    assert_eq!(diagnostics[1].message, "unused variable 'Unused'");
+    assert_eq!(diagnostics[2].severity, "note");
+    assert_eq!(diagnostics[3].severity, "error");
+    assert_eq!(diagnostics[3].line, Some(20));
+}
+
+#[test]
+fn test_is_error_line() {
+    assert!(is_error_line(
+        "[2024.05.02-10.21.44:512][  0]LogCook: Error: Unable to load /Game/Maps/Main"
+    ));
+    assert!(is_error_line("ERROR: Cook failed."));
+    assert!(is_error_line(
+        r"C:\Projects\Vestige\Source\Vestige\Private\VestigeCharacter.cpp(42): error C2065: 'Speed': undeclared identifier"
+    ));
+    assert!(!is_error_line(
+        "Result: Succeeded, 0 error(s), 0 warning(s)"
+    ));
+    assert!(!is_error_line("LogInit: Display: Errors will be logged"));
+    assert!(!is_error_line(
+        "File.cpp(10): warning C4996: 'GetErrorText': was declared deprecated"
+    ));
+}
@@ -0,0 +1,270 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use nu_protocol::{LabeledError, Span, Value, record};
+
+use super::ue_ini::{IniFile, IniValue};
+
+// Platform names of the Config folders, different from UnrealTargetPlatform (Win64)
+pub const CONFIG_PLATFORMS: &[&str] = &[
+    "Windows",
+    "Mac",
+    "Linux",
+    "LinuxArm64",
+    "Android",
+    "IOS",
+    "TVOS",
+    "VisionOS",
+];
+
+#[cfg(target_os = "windows")]
+pub const HOST_CONFIG_PLATFORM: &str = "Windows";
+#[cfg(target_os = "macos")]
+pub const HOST_CONFIG_PLATFORM: &str = "Mac";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub const HOST_CONFIG_PLATFORM: &str = "Linux";
+
+/**
+ * Value of a key after merging the config hierarchy, with the file that added it
+ */
+pub struct ConfigValue {
+    pub key: String,
+    pub value: IniValue,
+    pub file: PathBuf,
+}
+
+pub struct ConfigSection {
+    pub name: String,
+    pub values: Vec<ConfigValue>,
+}
+
+/**
+ * Get the ini files of a config type (Engine, Game, Input...) in the order they are loaded by the engine.
+ * The last file is saved by the editor (Saved/Config/WindowsEditor) or by the game (Saved/Config/Windows).
+ * https://dev.epicgames.com/documentation/en-us/unreal-engine/configuration-files-in-unreal-engine
+ */
+pub fn get_config_hierarchy(
+    unreal_engine_path: &PathBuf,
+    project_dir: &PathBuf,
+    config_type: &str,
+    platform: &str,
+    editor: bool,
+) -> Vec<PathBuf> {
+    let engine_config = unreal_engine_path.join("Engine/Config");
+    let project_config = project_dir.join("Config");
+    // Platform extensions of UE5 are in Platforms/<Platform>/Config
+    let engine_platform_configs = [
+        engine_config.join(platform),
+        unreal_engine_path
+            .join("Engine/Platforms")
+            .join(platform)
+            .join("Config"),
+    ];
+    let project_platform_configs = [
+        project_config.join(platform),
+        project_dir.join("Platforms").join(platform).join("Config"),
+    ];
+
+    let mut hierarchy = vec![
+        engine_config.join("Base.ini"),
+        engine_config.join(format!("Base{}.ini", config_type)),
+    ];
+    hierarchy.extend(
+        engine_platform_configs
+            .iter()
+            .map(|config| config.join(format!("Base{}{}.ini", platform, config_type))),
+    );
+    hierarchy.push(project_config.join(format!("Default{}.ini", config_type)));
+    hierarchy.extend(
+        engine_platform_configs
+            .iter()
+            .map(|config| config.join(format!("{}{}.ini", platform, config_type))),
+    );
+    hierarchy.extend(
+        project_platform_configs
+            .iter()
+            .map(|config| config.join(format!("{}{}.ini", platform, config_type))),
+    );
+    let saved = if editor {
+        format!("{}Editor", platform)
+    } else {
+        platform.to_string()
+    };
+    hierarchy.push(
+        project_dir
+            .join("Saved/Config")
+            .join(saved)
+            .join(format!("{}.ini", config_type)),
+    );
+    hierarchy
+}
+
+/**
+ * Merge the ini files in order, applying the operators like the engine:
+ * Key= replaces the values, +Key= adds a unique value, .Key= adds a value, -Key= removes a value and !Key= clears the values.
+ * Sections and keys are case insensitive, the first name found is kept. Missing files are skipped.
+ */
+pub fn merge_config_files(paths: &[PathBuf]) -> Result<Vec<ConfigSection>, LabeledError> {
+    let mut sections: Vec<ConfigSection> = vec![];
+    let mut section_indices: HashMap<String, usize> = HashMap::new();
+    for path in paths.iter().filter(|path| path.exists()) {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| LabeledError::new(format!("Failed to read {}: {}", path.display(), e)))?;
+        for section in IniFile::parse(&content).sections {
+            let index = *section_indices
+                .entry(section.name.to_lowercase())
+                .or_insert_with(|| {
+                    sections.push(ConfigSection {
+                        name: section.name.clone(),
+                        values: vec![],
+                    });
+                    sections.len() - 1
+                });
+            let values = &mut sections[index].values;
+            for entry in section.entries {
+                let same_key = |value: &ConfigValue| value.key.eq_ignore_ascii_case(&entry.key);
+                let same_value = |value: &ConfigValue| {
+                    same_key(value) && value.value.unquoted() == entry.value.unquoted()
+                };
+                let new_value = ConfigValue {
+                    key: entry.key.clone(),
+                    value: entry.value.clone(),
+                    file: path.clone(),
+                };
+                match entry.operator.as_str() {
+                    "+" => {
+                        if !values.iter().any(same_value) {
+                            values.push(new_value);
+                        }
+                    }
+                    "." => values.push(new_value),
+                    "-" => values.retain(|value| !same_value(value)),
+                    "!" => values.retain(|value| !same_key(value)),
+                    _ => match values.iter().position(same_key) {
+                        // Keep the position of the key in the section
+                        Some(position) => {
+                            values[position] = new_value;
+                            let mut current = 0;
+                            values.retain(|value| {
+                                let keep = current == position || !same_key(value);
+                                current += 1;
+                                keep
+                            });
+                        }
+                        None => values.push(new_value),
+                    },
+                }
+            }
+        }
+    }
+    Ok(sections)
+}
+
+impl ConfigSection {
+    pub fn to_values(&self, span: Span) -> Vec<Value> {
+        self.values
+            .iter()
+            .map(|value| {
+                Value::record(
+                    record! {
+                        "section" => Value::string(&self.name, span),
+                        "key" => Value::string(&value.key, span),
+                        "value" => value.value.to_value(span),
+                        "file" => Value::string(value.file.display().to_string(), span),
+                    },
+                    span,
+                )
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_get_config_hierarchy() {
+    let engine = PathBuf::from("UE_5.5");
+    let project = PathBuf::from("Project");
+    let hierarchy = get_config_hierarchy(&engine, &project, "Engine", "Windows", true);
+    assert_eq!(
+        hierarchy,
+        [
+            "UE_5.5/Engine/Config/Base.ini",
+            "UE_5.5/Engine/Config/BaseEngine.ini",
+            "UE_5.5/Engine/Config/Windows/BaseWindowsEngine.ini",
+            "UE_5.5/Engine/Platforms/Windows/Config/BaseWindowsEngine.ini",
+            "Project/Config/DefaultEngine.ini",
+            "UE_5.5/Engine/Config/Windows/WindowsEngine.ini",
+            "UE_5.5/Engine/Platforms/Windows/Config/WindowsEngine.ini",
+            "Project/Config/Windows/WindowsEngine.ini",
+            "Project/Platforms/Windows/Config/WindowsEngine.ini",
+            "Project/Saved/Config/WindowsEditor/Engine.ini",
+        ]
+        .map(PathBuf::from)
+    );
+
+    // The game only reads the config it saved
+    let hierarchy = get_config_hierarchy(&engine, &project, "Game", "Linux", false);
+    assert_eq!(
+        hierarchy.last(),
+        Some(&PathBuf::from("Project/Saved/Config/Linux/Game.ini"))
+    );
+}
+
+#[test]
+fn test_merge_config_files() {
+    let directory = std::env::temp_dir().join("nu_plugin_ue_test_merge_config_files");
+    std::fs::create_dir_all(&directory).unwrap();
+    let base = directory.join("BaseGame.ini");
+    let default = directory.join("DefaultGame.ini");
+    std::fs::write(
+        &base,
+        r#"[/Script/Engine.GameSession]
+MaxPlayers=16
++Maps=Main
++Maps=Test
+.Cultures=en
++Cultures=fr
++Servers=A
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        &default,
+        r#"[/script/engine.gamesession]
+maxplayers=8
++Maps=Main
+-Maps=Test
+.Cultures=en
+!Servers=ClearArray
++Servers=B
+"#,
+    )
+    .unwrap();
+
+    let sections =
+        merge_config_files(&[base.clone(), directory.join("Missing.ini"), default.clone()])
+            .unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    // The sections are the same whatever their case
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0].name, "/Script/Engine.GameSession");
+    let values: Vec<(&str, String, &PathBuf)> = sections[0]
+        .values
+        .iter()
+        .map(|value| (value.key.as_str(), value.value.to_string(), &value.file))
+        .collect();
+    assert_eq!(
+        values,
+        [
+            // Key= replaces the value at its position
+            ("maxplayers", "8".to_string(), &default),
+            // +Key= does not add a value twice, -Key= removes it
+            ("Maps", "Main".to_string(), &base),
+            // .Key= adds the value even if it is already set
+            ("Cultures", "en".to_string(), &base),
+            ("Cultures", "fr".to_string(), &base),
+            ("Cultures", "en".to_string(), &default),
+            // !Key= clears the values set before
+            ("Servers", "B".to_string(), &default),
+        ]
+    );
+}
@@ -0,0 +1,349 @@
+use std::path::PathBuf;
+
+use nu_protocol::LabeledError;
+
+// https://github.com/EpicGames/UnrealEngine/blob/release/Engine/Source/Developer/DesktopPlatform/Private/DesktopPlatformBase.cpp
+
+/**
+ * Where an Unreal Engine path was found
+ */
+#[derive(Clone, Copy, PartialEq)]
+pub enum EngineSource {
+    Launcher,
+    Registered,
+    RelativePath,
+    ParentDirectory,
+    Solution,
+    PipInstall,
+    SearchPath,
+}
+
+impl std::fmt::Display for EngineSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EngineSource::Launcher => write!(f, "Launcher"),
+            EngineSource::Registered => write!(f, "Registered"),
+            EngineSource::RelativePath => write!(f, "RelativePath"),
+            EngineSource::ParentDirectory => write!(f, "ParentDirectory"),
+            EngineSource::Solution => write!(f, "Solution"),
+            EngineSource::PipInstall => write!(f, "PipInstall"),
+            EngineSource::SearchPath => write!(f, "SearchPath"),
+        }
+    }
+}
+
+pub struct EngineInstallation {
+    pub identifier: String,
+    pub path: PathBuf,
+    pub source: EngineSource,
+}
+
+#[cfg(not(target_os = "windows"))]
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+// Get LauncherInstalled.dat path, the Epic Games Launcher is not available on Linux
+#[cfg(target_os = "windows")]
+pub fn get_launcher_installed_path() -> Option<PathBuf> {
+    let program_data = std::env::var_os("PROGRAMDATA")
+        .map(PathBuf::from)
+        .unwrap_or(PathBuf::from("C:/ProgramData"));
+    Some(program_data.join("Epic/UnrealEngineLauncher/LauncherInstalled.dat"))
+}
+
+#[cfg(target_os = "macos")]
+pub fn get_launcher_installed_path() -> Option<PathBuf> {
+    home_dir().map(|home| {
+        home.join("Library/Application Support/Epic/UnrealEngineLauncher/LauncherInstalled.dat")
+    })
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn get_launcher_installed_path() -> Option<PathBuf> {
+    None
+}
+
+/**
+ * Returns the engines installed with the Epic Games Launcher, identified by their version (e.g. "5.5")
+ */
+pub fn get_launcher_installations() -> Result<Vec<EngineInstallation>, LabeledError> {
+    let launcher_installed_path = match get_launcher_installed_path() {
+        Some(path) if path.exists() => path,
+        _ => return Ok(vec![]),
+    };
+    let content = std::fs::read_to_string(&launcher_installed_path).map_err(|e| {
+        LabeledError::new(format!(
+            "Failed to read {}: {}",
+            launcher_installed_path.display(),
+            e.to_string()
+        ))
+    })?;
+    let json: serde_json::Value = serde_json::from_str(&content).map_err(|e| {
+        LabeledError::new(format!(
+            "Failed to parse {}: {}",
+            launcher_installed_path.display(),
+            e.to_string()
+        ))
+    })?;
+
+    Ok(parse_launcher_installed(&json))
+}
+
+// {"InstallationList": [{"InstallLocation": "C:\\Program Files\\Epic Games\\UE_5.5", "AppName": "UE_5.5", ...}]}
+fn parse_launcher_installed(json: &serde_json::Value) -> Vec<EngineInstallation> {
+    json.get("InstallationList")
+        .and_then(|v| v.as_array())
+        .map(|list| list.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|installation| {
+            let app_name = installation.get("AppName")?.as_str()?;
+            let install_location = installation.get("InstallLocation")?.as_str()?;
+            Some(EngineInstallation {
+                identifier: app_name.strip_prefix("UE_")?.to_string(),
+                path: PathBuf::from(install_location),
+                source: EngineSource::Launcher,
+            })
+        })
+        .collect()
+}
+
+// Registered builds are stored in HKCU\SOFTWARE\Epic Games\Unreal Engine\Builds
+#[cfg(target_os = "windows")]
+fn get_registered_builds() -> Result<Vec<(String, String)>, LabeledError> {
+    let output = std::process::Command::new("reg")
+        .args(["query", r"HKCU\SOFTWARE\Epic Games\Unreal Engine\Builds"])
+        .output()
+        .map_err(|e| {
+            LabeledError::new(format!(
+                "Failed to query registered builds: {}",
+                e.to_string()
+            ))
+        })?;
+    if !output.status.success() {
+        // The key does not exist when no build was registered
+        return Ok(vec![]);
+    }
+
+    let re = regex::Regex::new(r"^\s+(\S+)\s+REG_SZ\s+(.+)$").unwrap();
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| re.captures(line))
+        .map(|cap| (cap[1].to_string(), cap[2].trim().to_string()))
+        .collect())
+}
+
+// Registered builds are stored in the [Installations] section of Install.ini
+#[cfg(not(target_os = "windows"))]
+fn get_registered_builds() -> Result<Vec<(String, String)>, LabeledError> {
+    #[cfg(target_os = "macos")]
+    const INSTALL_INI: &str = "Library/Application Support/Epic/UnrealEngine/Install.ini";
+    #[cfg(not(target_os = "macos"))]
+    const INSTALL_INI: &str = ".config/Epic/UnrealEngine/Install.ini";
+
+    let install_ini_path = match home_dir() {
+        Some(home) if home.join(INSTALL_INI).exists() => home.join(INSTALL_INI),
+        _ => return Ok(vec![]),
+    };
+    let content = std::fs::read_to_string(&install_ini_path).map_err(|e| {
+        LabeledError::new(format!(
+            "Failed to read {}: {}",
+            install_ini_path.display(),
+            e.to_string()
+        ))
+    })?;
+
+    Ok(parse_install_ini(&content))
+}
+
+// [Installations]
+// {5C3A4C1B-4F67-4E2B-9A1D-3C2B1A0F9E8D}=/home/user/UnrealEngine
+#[cfg_attr(target_os = "windows", allow(dead_code))]
+fn parse_install_ini(content: &str) -> Vec<(String, String)> {
+    let mut builds = vec![];
+    let mut in_installations = false;
+    for line in content.lines().map(|line| line.trim()) {
+        if line.starts_with('[') {
+            in_installations = line == "[Installations]";
+        } else if let Some((identifier, path)) = line.split_once('=') {
+            if in_installations {
+                builds.push((identifier.trim().to_string(), path.trim().to_string()));
+            }
+        }
+    }
+    builds
+}
+
+/**
+ * Returns the engines registered with UnrealVersionSelector, identified by a GUID
+ */
+pub fn get_registered_installations() -> Result<Vec<EngineInstallation>, LabeledError> {
+    Ok(get_registered_builds()?
+        .into_iter()
+        .map(|(identifier, path)| EngineInstallation {
+            identifier,
+            path: PathBuf::from(path),
+            source: EngineSource::Registered,
+        })
+        .collect())
+}
+
+/**
+ * Returns true if the identifiers are the same, ignoring the braces and case of GUIDs
+ */
+pub fn is_same_identifier(a: &str, b: &str) -> bool {
+    let trim = |s: &str| s.trim_start_matches('{').trim_end_matches('}').to_string();
+    trim(a).eq_ignore_ascii_case(&trim(b))
+}
+
+/**
+ * Returns true if the directory is the root of an Unreal Engine (contains Engine/Build/Build.version)
+ */
+pub fn is_engine_root(path: &PathBuf) -> bool {
+    path.join("Engine/Build/Build.version").exists()
+}
+
+/**
+ * Returns true if the engine was installed with the Launcher or built with BuildGraph, false for source builds
+ */
+pub fn is_installed_build(path: &PathBuf) -> bool {
+    path.join("Engine/Build/InstalledBuild.txt").exists()
+}
+
+/**
+ * Returns the engines found in the search paths. A search path is either an engine root or a directory containing engine roots.
+ */
+pub fn get_search_path_installations(search_paths: &[PathBuf]) -> Vec<EngineInstallation> {
+    let mut installations = vec![];
+    for search_path in search_paths {
+        let engine_roots: Vec<PathBuf> = if is_engine_root(search_path) {
+            vec![search_path.clone()]
+        } else {
+            match search_path.read_dir() {
+                Ok(entries) => entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(is_engine_root)
+                    .collect(),
+                Err(_) => vec![],
+            }
+        };
+        installations.extend(engine_roots.into_iter().map(|path| {
+            EngineInstallation {
+                identifier: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                path,
+                source: EngineSource::SearchPath,
+            }
+        }));
+    }
+    installations
+}
+
+// Engine/Build/Build.version
+pub struct BuildVersion {
+    pub major: i64,
+    pub minor: i64,
+    pub patch: i64,
+    pub changelist: i64,
+    pub branch_name: String,
+}
+
+impl std::fmt::Display for BuildVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/**
+ * Returns the version of the engine, or None if Engine/Build/Build.version does not exist
+ */
+pub fn get_build_version(path: &PathBuf) -> Result<Option<BuildVersion>, LabeledError> {
+    let build_version_path = path.join("Engine/Build/Build.version");
+    if !build_version_path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&build_version_path).map_err(|e| {
+        LabeledError::new(format!(
+            "Failed to read {}: {}",
+            build_version_path.display(),
+            e.to_string()
+        ))
+    })?;
+    let json: serde_json::Value = serde_json::from_str(&content).map_err(|e| {
+        LabeledError::new(format!(
+            "Failed to parse {}: {}",
+            build_version_path.display(),
+            e.to_string()
+        ))
+    })?;
+
+    let get_int = |key: &str| json.get(key).and_then(|v| v.as_i64()).unwrap_or(0);
+    Ok(Some(BuildVersion {
+        major: get_int("MajorVersion"),
+        minor: get_int("MinorVersion"),
+        patch: get_int("PatchVersion"),
+        changelist: get_int("Changelist"),
+        branch_name: json
+            .get("BranchName")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+    }))
+}
+
+#[test]
+fn test_parse_launcher_installed() {
+    let json: serde_json::Value = serde_json::from_str(
+        r#"{
+	"InstallationList": [
+		{
+			"InstallLocation": "C:\\Program Files\\Epic Games\\UE_5.4",
+			"NamespaceId": "ue",
+			"ItemId": "3b4a4b6cd5a54ea4a6a44c6ad5a0d6e2",
+			"ArtifactId": "UE_5.4",
+			"AppVersion": "5.4.4-35576357+++UE5+Release-5.4-Windows",
+			"AppName": "UE_5.4"
+		},
+		{
+			"InstallLocation": "C:\\Program Files\\Epic Games\\Fortnite",
+			"AppName": "Fortnite"
+		}
+	]
+}"#,
+    )
+    .unwrap();
+    let installations = parse_launcher_installed(&json);
+
+    // Only the engines are listed, identified by their version
+    assert_eq!(installations.len(), 1);
+    assert_eq!(installations[0].identifier, "5.4");
+    assert_eq!(
+        installations[0].path,
+        PathBuf::from("C:\\Program Files\\Epic Games\\UE_5.4")
+    );
+    assert!(installations[0].source == EngineSource::Launcher);
+    assert!(parse_launcher_installed(&serde_json::Value::Null).is_empty());
+}
+
+#[test]
+fn test_parse_install_ini() {
+    let content = "[Installations]\n{5C3A4C1B-4F67-4E2B-9A1D-3C2B1A0F9E8D}=/home/user/UnrealEngine\nUE_Source = /opt/UnrealEngine \n\n[Other]\nKey=Value\n";
+    assert_eq!(
+        parse_install_ini(content),
+        vec![
+            (
+                "{5C3A4C1B-4F67-4E2B-9A1D-3C2B1A0F9E8D}".to_string(),
+                "/home/user/UnrealEngine".to_string()
+            ),
+            ("UE_Source".to_string(), "/opt/UnrealEngine".to_string()),
+        ]
+    );
+    assert!(is_same_identifier(
+        "{5c3a4c1b-4f67-4e2b-9a1d-3c2b1a0f9e8d}",
+        "5C3A4C1B-4F67-4E2B-9A1D-3C2B1A0F9E8D"
+    ));
+}
@@ -0,0 +1,773 @@
+use std::fmt::Display;
+
+use nu_protocol::{LabeledError, Record, Span, Value, record};
+
+/**
+ * Value of an ini entry, struct literals (A=1,B="x") and arrays (1,2,3) are parsed recursively.
+ * Strings are kept as written with their quotes, also in structs and arrays, so the values that are not edited are written back the same.
+ * Strings without quotes are quoted when needed in structs and arrays, (Name=A, B) is written (Name="A, B").
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum IniValue {
+    String(String),
+    Struct(Vec<(String, IniValue)>),
+    Array(Vec<IniValue>),
+}
+
+/**
+ * Entry of a section, the operator is one of "", "+" (add unique), "-" (remove), "." (add) or "!" (clear)
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct IniEntry {
+    pub operator: String,
+    pub key: String,
+    pub value: IniValue,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IniSection {
+    pub name: String,
+    pub entries: Vec<IniEntry>,
+}
+
+/**
+ * Unreal Engine config file, sections and entries are kept in order with duplicate keys.
+ * Comments and empty lines are not kept, use parse_line to edit a file in place.
+ */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IniFile {
+    pub sections: Vec<IniSection>,
+}
+
+pub enum IniLine {
+    Section(String),
+    Entry(IniEntry),
+    // Comments, empty lines and lines without '='
+    Other,
+}
+
+const OPERATORS: &[char] = &['+', '-', '.', '!'];
+
+// Split at the commas outside of quotes and parentheses
+fn split_items(content: &str) -> Vec<&str> {
+    let mut items = vec![];
+    let (mut depth, mut in_quotes, mut escaped, mut start) = (0, false, false, 0);
+    for (index, c) in content.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            '(' if !in_quotes => depth += 1,
+            ')' if !in_quotes => depth -= 1,
+            ',' if !in_quotes && depth == 0 => {
+                items.push(&content[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&content[start..]);
+    items
+}
+
+// Find the '=' of a struct member, e.g. Path="/Game/UI" or Scale(0)=1
+fn find_member_separator(item: &str) -> Option<usize> {
+    let index = item.find('=')?;
+    let name = &item[..index];
+    let is_identifier = name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_' || c == '(' || c == ')' || c == '[' || c == ']');
+    let starts_with_letter = name.starts_with(|c: char| c.is_alphanumeric() || c == '_');
+    (starts_with_letter && is_identifier).then_some(index)
+}
+
+fn is_quoted(value: &str) -> bool {
+    value.len() >= 2 && value.starts_with('"') && value.ends_with('"')
+}
+
+fn unquote(value: &str) -> String {
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(value) => value.replace("\\\"", "\"").replace("\\\\", "\\"),
+        None => value.to_string(),
+    }
+}
+
+fn quote_if_needed(value: &str) -> String {
+    let needs_quotes = value
+        .chars()
+        .any(|c| c.is_whitespace() || matches!(c, ',' | '(' | ')' | '=' | '"'));
+    if needs_quotes {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Parse the content of parentheses or an item of a struct or array
+fn parse_nested_value(value: &str) -> IniValue {
+    let value = value.trim();
+    match value.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+        Some(content) if content.trim().is_empty() => IniValue::Array(vec![]),
+        Some(content) => {
+            let items = split_items(content);
+            let members: Option<Vec<(String, IniValue)>> = items
+                .iter()
+                .map(|item| {
+                    let item = item.trim();
+                    let index = find_member_separator(item)?;
+                    Some((
+                        item[..index].to_string(),
+                        parse_nested_value(&item[index + 1..]),
+                    ))
+                })
+                .collect();
+            match members {
+                Some(members) => IniValue::Struct(members),
+                None => IniValue::Array(items.into_iter().map(parse_nested_value).collect()),
+            }
+        }
+        None => IniValue::String(value.to_string()),
+    }
+}
+
+impl IniValue {
+    pub fn parse(value: &str) -> IniValue {
+        let trimmed = value.trim();
+        if trimmed.starts_with('(') && trimmed.ends_with(')') {
+            parse_nested_value(trimmed)
+        } else {
+            IniValue::String(value.to_string())
+        }
+    }
+
+    /**
+     * Value with the strings of structs and arrays unquoted, to compare values written with and without quotes:
+     * the engine reads (FilePath="/Game/Maps/Main") and (FilePath=/Game/Maps/Main) the same
+     */
+    pub fn unquoted(&self) -> IniValue {
+        match self {
+            IniValue::String(value) => IniValue::String(value.clone()),
+            IniValue::Struct(members) => IniValue::Struct(
+                members
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.unquoted_nested()))
+                    .collect(),
+            ),
+            IniValue::Array(items) => {
+                IniValue::Array(items.iter().map(IniValue::unquoted_nested).collect())
+            }
+        }
+    }
+
+    fn unquoted_nested(&self) -> IniValue {
+        match self {
+            IniValue::String(value) => IniValue::String(unquote(value)),
+            _ => self.unquoted(),
+        }
+    }
+
+    fn format_nested(&self) -> String {
+        match self {
+            IniValue::String(value) if is_quoted(value) => value.clone(),
+            IniValue::String(value) => quote_if_needed(value),
+            _ => self.to_string(),
+        }
+    }
+
+    pub fn to_value(&self, span: Span) -> Value {
+        match self {
+            IniValue::String(value) => Value::string(value, span),
+            IniValue::Struct(members) => Value::record(
+                members
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.to_value(span)))
+                    .collect(),
+                span,
+            ),
+            IniValue::Array(items) => {
+                Value::list(items.iter().map(|item| item.to_value(span)).collect(), span)
+            }
+        }
+    }
+
+    pub fn from_value(value: &Value) -> Result<IniValue, LabeledError> {
+        match value {
+            Value::Record { val, .. } => Ok(IniValue::Struct(
+                val.iter()
+                    .map(|(name, value)| Ok((name.clone(), IniValue::from_value(value)?)))
+                    .collect::<Result<_, LabeledError>>()?,
+            )),
+            Value::List { vals, .. } => Ok(IniValue::Array(
+                vals.iter()
+                    .map(IniValue::from_value)
+                    .collect::<Result<_, LabeledError>>()?,
+            )),
+            Value::Nothing { .. } => Ok(IniValue::String(String::new())),
+            Value::String { val, .. } => Ok(IniValue::String(val.clone())),
+            Value::Int { .. } | Value::Float { .. } | Value::Bool { .. } => Ok(IniValue::String(
+                value.to_expanded_string("", &Default::default()),
+            )),
+            _ => Err(LabeledError::new(format!(
+                "Unsupported ini value of type {}",
+                value.get_type()
+            ))
+            .with_label("Expected a string, a record or a list", value.span())),
+        }
+    }
+}
+
+impl Display for IniValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IniValue::String(value) => write!(f, "{}", value),
+            IniValue::Struct(members) => {
+                let members: Vec<String> = members
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, value.format_nested()))
+                    .collect();
+                write!(f, "({})", members.join(","))
+            }
+            IniValue::Array(items) => {
+                let items: Vec<String> = items.iter().map(|item| item.format_nested()).collect();
+                write!(f, "({})", items.join(","))
+            }
+        }
+    }
+}
+
+impl IniEntry {
+    pub fn to_value(&self, span: Span) -> Value {
+        Value::record(
+            record! {
+                "operator" => Value::string(&self.operator, span),
+                "key" => Value::string(&self.key, span),
+                "value" => self.value.to_value(span),
+            },
+            span,
+        )
+    }
+
+    pub fn from_value(value: &Value) -> Result<IniEntry, LabeledError> {
+        let record = value.as_record().map_err(|_| {
+            LabeledError::new("Expected a record {operator, key, value}")
+                .with_label("Not a record", value.span())
+        })?;
+        let key = record
+            .get("key")
+            .ok_or_else(|| {
+                LabeledError::new("Missing key in ini entry")
+                    .with_label("Missing key", value.span())
+            })?
+            .coerce_string()?;
+        let operator = match record.get("operator") {
+            Some(operator) => operator.coerce_string()?,
+            None => String::new(),
+        };
+        if operator.len() > 1 || !operator.chars().all(|c| OPERATORS.contains(&c)) {
+            return Err(
+                LabeledError::new(format!("Invalid ini operator {}", operator))
+                    .with_label("Invalid operator", value.span())
+                    .with_help("Use one of \"\", \"+\", \"-\", \".\" or \"!\""),
+            );
+        }
+        let value = match record.get("value") {
+            Some(value) => IniValue::from_value(value)?,
+            None => IniValue::String(String::new()),
+        };
+        Ok(IniEntry {
+            operator,
+            key,
+            value,
+        })
+    }
+}
+
+impl Display for IniEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // !Key clears the array without value
+        if self.operator == "!" && self.value == IniValue::String(String::new()) {
+            write!(f, "!{}=ClearArray", self.key)
+        } else {
+            write!(f, "{}{}={}", self.operator, self.key, self.value)
+        }
+    }
+}
+
+/**
+ * Parse a line of an ini file
+ */
+pub fn parse_line(line: &str) -> IniLine {
+    // The UTF-8 BOM is kept in the first line of the files edited in place
+    let trimmed = line.trim_start_matches('\u{feff}').trim();
+    if trimmed.starts_with(';') || trimmed.starts_with('#') {
+        return IniLine::Other;
+    }
+    if let Some(name) = trimmed.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+        return IniLine::Section(name.to_string());
+    }
+    let Some((key, value)) = trimmed.split_once('=') else {
+        return IniLine::Other;
+    };
+    let (operator, key) = match key.chars().next() {
+        Some(c) if OPERATORS.contains(&c) => (c.to_string(), &key[1..]),
+        _ => (String::new(), key),
+    };
+    IniLine::Entry(IniEntry {
+        operator,
+        key: key.trim().to_string(),
+        value: IniValue::parse(value),
+    })
+}
+
+impl IniFile {
+    pub fn parse(content: &str) -> IniFile {
+        // Files saved by some editors start with a UTF-8 BOM
+        let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+        let mut file = IniFile::default();
+        for line in content.lines() {
+            match parse_line(line) {
+                IniLine::Section(name) => file.sections.push(IniSection {
+                    name,
+                    entries: vec![],
+                }),
+                IniLine::Entry(entry) => {
+                    // Entries before the first section
+                    if file.sections.is_empty() {
+                        file.sections.push(IniSection {
+                            name: String::new(),
+                            entries: vec![],
+                        });
+                    }
+                    file.sections.last_mut().unwrap().entries.push(entry);
+                }
+                IniLine::Other => {}
+            }
+        }
+        file
+    }
+
+    /**
+     * Record of the sections, each section is a list of {operator, key, value}
+     */
+    pub fn to_value(&self, span: Span) -> Value {
+        let mut record = Record::new();
+        for section in &self.sections {
+            let entries = section.entries.iter().map(|entry| entry.to_value(span));
+            // Sections can be repeated in a file, their entries are appended
+            match record.get_mut(&section.name) {
+                Some(Value::List { vals, .. }) => vals.extend(entries),
+                _ => {
+                    record.push(&section.name, Value::list(entries.collect(), span));
+                }
+            }
+        }
+        Value::record(record, span)
+    }
+
+    pub fn from_value(value: &Value) -> Result<IniFile, LabeledError> {
+        let record = value.as_record().map_err(|_| {
+            LabeledError::new("Expected a record of sections")
+                .with_label("Not a record", value.span())
+        })?;
+        let sections = record
+            .iter()
+            .map(|(name, entries)| {
+                let entries = match entries {
+                    Value::List { vals, .. } => vals
+                        .iter()
+                        .map(IniEntry::from_value)
+                        .collect::<Result<_, LabeledError>>()?,
+                    // Simple sections can be written as records {key: value}
+                    Value::Record { val, .. } => val
+                        .iter()
+                        .map(|(key, value)| {
+                            Ok(IniEntry {
+                                operator: String::new(),
+                                key: key.clone(),
+                                value: IniValue::from_value(value)?,
+                            })
+                        })
+                        .collect::<Result<_, LabeledError>>()?,
+                    _ => {
+                        return Err(LabeledError::new(format!(
+                            "Expected a list of entries for section {}",
+                            name
+                        ))
+                        .with_label("Not a list", entries.span()));
+                    }
+                };
+                Ok(IniSection {
+                    name: name.clone(),
+                    entries,
+                })
+            })
+            .collect::<Result<_, LabeledError>>()?;
+        Ok(IniFile { sections })
+    }
+}
+
+impl Display for IniFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, section) in self.sections.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            if !section.name.is_empty() {
+                writeln!(f, "[{}]", section.name)?;
+            }
+            for entry in &section.entries {
+                writeln!(f, "{}", entry)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/**
+ * Edit of a key in a config file
+ */
+pub enum IniEdit {
+    // Key=Value, replacing the values of the key
+    Set(IniValue),
+    // +Key=Value if the value is not already added
+    Append(IniValue),
+    // Remove the lines adding the value, or -Key=Value to remove an inherited value.
+    // Without value, remove all the lines of the key.
+    Remove(Option<IniValue>),
+}
+
+/**
+ * Edit a key of a section, keeping the comments, the order and the other lines as they are.
+ * The section is added at the end of the file if it does not exist.
+ */
+pub fn edit_ini(content: &str, section: &str, key: &str, edit: IniEdit) -> String {
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    // The file keeps its ending, new files end with a newline
+    let ending = if content.is_empty() || content.ends_with('\n') {
+        newline
+    } else {
+        ""
+    };
+    let mut lines: Vec<String> = content.lines().map(|line| line.to_string()).collect();
+
+    let section_start = lines.iter().position(
+        |line| matches!(parse_line(line), IniLine::Section(name) if name.eq_ignore_ascii_case(section)),
+    );
+    let Some(section_start) = section_start else {
+        let line = match &edit {
+            IniEdit::Set(value) => format!("{}={}", key, value),
+            IniEdit::Append(value) => format!("+{}={}", key, value),
+            IniEdit::Remove(Some(value)) => format!("-{}={}", key, value),
+            IniEdit::Remove(None) => return content.to_string(),
+        };
+        if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+            lines.push(String::new());
+        }
+        lines.push(format!("[{}]", section));
+        lines.push(line);
+        return lines.join(newline) + ending;
+    };
+    let section_end = lines
+        .iter()
+        .skip(section_start + 1)
+        .position(|line| matches!(parse_line(line), IniLine::Section(_)))
+        .map_or(lines.len(), |position| section_start + 1 + position);
+
+    // Lines of the section with the key: (line index, operator, unquoted value)
+    let key_lines: Vec<(usize, String, IniValue)> = (section_start + 1..section_end)
+        .filter_map(|index| match parse_line(&lines[index]) {
+            IniLine::Entry(entry) if entry.key.eq_ignore_ascii_case(key) => {
+                Some((index, entry.operator, entry.value.unquoted()))
+            }
+            _ => None,
+        })
+        .collect();
+    // New lines are added after the key, or after the last line of the section
+    let insert_index = key_lines.last().map_or_else(
+        || {
+            (section_start + 1..section_end)
+                .rev()
+                .find(|&index| !lines[index].trim().is_empty())
+                .map_or(section_start + 1, |index| index + 1)
+        },
+        |(index, _, _)| index + 1,
+    );
+
+    // Values are compared as parsed from the lines, the string (A=1) is the struct of +Key=(A=1).
+    // The lines are written with the value as given to keep its quotes.
+    let parse_value = |value: &IniValue| IniValue::parse(&value.to_string()).unquoted();
+
+    let mut removed_lines: Vec<usize> = vec![];
+    match edit {
+        IniEdit::Set(value) => {
+            let set_lines: Vec<usize> = key_lines
+                .iter()
+                .filter(|(_, operator, _)| operator.is_empty())
+                .map(|(index, _, _)| *index)
+                .collect();
+            let line = format!("{}={}", key, value);
+            match set_lines.split_first() {
+                Some((first, others)) => {
+                    lines[*first] = line;
+                    removed_lines.extend(others);
+                }
+                None => lines.insert(insert_index, line),
+            }
+        }
+        IniEdit::Append(value) => {
+            let parsed_value = parse_value(&value);
+            let is_added = key_lines
+                .iter()
+                .any(|(_, operator, line_value)| operator == "+" && *line_value == parsed_value);
+            if !is_added {
+                lines.insert(insert_index, format!("+{}={}", key, value));
+            }
+        }
+        IniEdit::Remove(None) => {
+            removed_lines.extend(key_lines.iter().map(|(index, _, _)| *index));
+        }
+        IniEdit::Remove(Some(value)) => {
+            let parsed_value = parse_value(&value);
+            let adding_lines: Vec<usize> = key_lines
+                .iter()
+                .filter(|(_, operator, line_value)| operator != "-" && *line_value == parsed_value)
+                .map(|(index, _, _)| *index)
+                .collect();
+            let is_removed = key_lines
+                .iter()
+                .any(|(_, operator, line_value)| operator == "-" && *line_value == parsed_value);
+            if !adding_lines.is_empty() {
+                removed_lines.extend(adding_lines);
+            } else if !is_removed {
+                lines.insert(insert_index, format!("-{}={}", key, value));
+            }
+        }
+    }
+
+    let mut current = 0;
+    lines.retain(|_| {
+        let keep = !removed_lines.contains(&current);
+        current += 1;
+        keep
+    });
+    lines.join(newline) + ending
+}
+
+#[test]
+fn test_parse_ini() {
+    let content = r#"; Comment
+[/Script/EngineSettings.GameMapsSettings]
+GameDefaultMap=/Game/Maps/Main.Main
+
+[/Script/UnrealEd.ProjectPackagingSettings]
++MapsToCook=(FilePath="/Game/Maps/Main")
++DirectoriesToAlwaysCook=(Path="/Game/UI")
+-MapsToCook=(FilePath="/Game/Maps/Test")
+!Cultures=ClearArray
+.Cultures=en
+.Cultures=en
+
+[/Script/Engine.AssetManagerSettings]
++PrimaryAssetTypesToScan=(PrimaryAssetType="Map",AssetBaseClass=/Script/Engine.World,Directories=((Path="/Game/Maps")),SpecificAssets=,Rules=(Priority=-1,ChunkId=-1))
+"#;
+    let file = IniFile::parse(content);
+    assert_eq!(file.sections.len(), 3);
+
+    let packaging = &file.sections[1].entries;
+    assert_eq!(packaging.len(), 6);
+    assert_eq!(packaging[0].operator, "+");
+    assert_eq!(
+        packaging[0].value,
+        IniValue::Struct(vec![(
+            "FilePath".to_string(),
+            IniValue::String("\"/Game/Maps/Main\"".to_string())
+        )])
+    );
+    assert_eq!(packaging[2].operator, "-");
+    assert_eq!(packaging[3].operator, "!");
+    assert_eq!(packaging[4].key, "Cultures");
+
+    let IniValue::Struct(members) = &file.sections[2].entries[0].value else {
+        panic!("PrimaryAssetTypesToScan is not a struct");
+    };
+    assert_eq!(
+        members[2],
+        (
+            "Directories".to_string(),
+            IniValue::Array(vec![IniValue::Struct(vec![(
+                "Path".to_string(),
+                IniValue::String("\"/Game/Maps\"".to_string())
+            )])])
+        )
+    );
+    assert_eq!(members[3].1, IniValue::String(String::new()));
+
+    // The operators, order, struct literals and quotes are written back as they are
+    let written = file.to_string();
+    for line in content
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with(';'))
+    {
+        assert!(
+            written.lines().any(|written_line| written_line == line),
+            "{}",
+            line
+        );
+    }
+    assert_eq!(IniFile::parse(&written), file);
+
+    // The strings set without quotes are quoted when needed
+    let value = IniValue::Struct(vec![
+        ("Name".to_string(), IniValue::String("A, B".to_string())),
+        ("Path".to_string(), IniValue::String("/Game/UI".to_string())),
+    ]);
+    assert_eq!(value.to_string(), "(Name=\"A, B\",Path=/Game/UI)");
+}
+
+#[test]
+fn test_parse_ini_bom() {
+    let file = IniFile::parse(
+        "\u{feff}[/Script/EngineSettings.GeneralProjectSettings]\r\nProjectVersion=1.0.0\r\n",
+    );
+    assert_eq!(file.sections.len(), 1);
+    assert_eq!(
+        file.sections[0].name,
+        "/Script/EngineSettings.GeneralProjectSettings"
+    );
+    assert_eq!(
+        file.sections[0].entries[0].value,
+        IniValue::String("1.0.0".to_string())
+    );
+}
+
+#[test]
+fn test_edit_ini() {
+    let content = "; Project settings\r\n[/Script/EngineSettings.GeneralProjectSettings]\r\nProjectID=ABC\r\nProjectVersion=1.0.0\r\n\r\n[/Script/Engine.RendererSettings]\r\n+ConsoleVariables=r.Shadow=1\r\n";
+    let section = "/Script/EngineSettings.GeneralProjectSettings";
+
+    let edited = edit_ini(
+        content,
+        section,
+        "ProjectVersion",
+        IniEdit::Set(IniValue::String("1.1.0".to_string())),
+    );
+    assert_eq!(
+        edited,
+        content.replace("ProjectVersion=1.0.0", "ProjectVersion=1.1.0")
+    );
+
+    let edited = edit_ini(
+        content,
+        section,
+        "Description",
+        IniEdit::Set(IniValue::String("Game".to_string())),
+    );
+    assert!(edited.contains(
+        "ProjectVersion=1.0.0\r\nDescription=Game\r\n\r\n[/Script/Engine.RendererSettings]"
+    ));
+
+    let renderer = "/Script/Engine.RendererSettings";
+    let value = IniValue::String("r.Shadow=1".to_string());
+    assert_eq!(
+        edit_ini(
+            content,
+            renderer,
+            "ConsoleVariables",
+            IniEdit::Append(value.clone())
+        ),
+        content
+    );
+    assert_eq!(
+        edit_ini(
+            content,
+            renderer,
+            "ConsoleVariables",
+            IniEdit::Remove(Some(value))
+        ),
+        content.replace("+ConsoleVariables=r.Shadow=1\r\n", "")
+    );
+
+    let edited = edit_ini(
+        content,
+        "/Script/Engine.Engine",
+        "bSmoothFrameRate",
+        IniEdit::Set(IniValue::String("True".to_string())),
+    );
+    assert!(edited.ends_with(
+        "+ConsoleVariables=r.Shadow=1\r\n\r\n[/Script/Engine.Engine]\r\nbSmoothFrameRate=True\r\n"
+    ));
+
+    // The appended value is compared with the parsed lines
+    let content = "[/Script/Engine.Engine]\n+ActiveGameNameRedirects=(OldGameName=\"/Script/Old\",NewGameName=\"/Script/New\")\n";
+    let value =
+        IniValue::String("(OldGameName=\"/Script/Old\",NewGameName=\"/Script/New\")".to_string());
+    let edited = edit_ini(
+        content,
+        "/Script/Engine.Engine",
+        "ActiveGameNameRedirects",
+        IniEdit::Append(value.clone()),
+    );
+    assert_eq!(edited, content);
+    assert_eq!(
+        edit_ini(
+            content,
+            "/Script/Engine.Engine",
+            "ActiveGameNameRedirects",
+            IniEdit::Remove(Some(value.clone()))
+        ),
+        "[/Script/Engine.Engine]\n"
+    );
+    // The value of `ue config set` has no quotes, the quotes of the lines are ignored
+    let unquoted_value = IniValue::Struct(vec![
+        (
+            "OldGameName".to_string(),
+            IniValue::String("/Script/Old".to_string()),
+        ),
+        (
+            "NewGameName".to_string(),
+            IniValue::String("/Script/New".to_string()),
+        ),
+    ]);
+    assert_eq!(
+        edit_ini(
+            content,
+            "/Script/Engine.Engine",
+            "ActiveGameNameRedirects",
+            IniEdit::Remove(Some(unquoted_value))
+        ),
+        "[/Script/Engine.Engine]\n"
+    );
+
+    let append = |content: &str| {
+        edit_ini(
+            content,
+            "/Script/Engine.Engine",
+            "Struct",
+            IniEdit::Append(IniValue::String("(A=1)".to_string())),
+        )
+    };
+    let edited = append(&append("[/Script/Engine.Engine]\n"));
+    assert_eq!(edited, "[/Script/Engine.Engine]\n+Struct=(A=1)\n");
+    let edited = edit_ini(
+        "[/Script/Engine.Engine]\n",
+        "/Script/Engine.Engine",
+        "ActiveGameNameRedirects",
+        IniEdit::Append(value),
+    );
+    assert_eq!(edited, content);
+
+    // The file keeps its ending
+    assert_eq!(
+        append("[/Script/Engine.Engine]"),
+        "[/Script/Engine.Engine]\n+Struct=(A=1)"
+    );
+    assert_eq!(append(""), "[/Script/Engine.Engine]\n+Struct=(A=1)\n");
+
+    // The first section is found in files starting with a BOM
+    let edited = append("\u{feff}[/Script/Engine.Engine]\n");
+    assert_eq!(edited, "\u{feff}[/Script/Engine.Engine]\n+Struct=(A=1)\n");
+}
@@ -1,23 +1,70 @@
 use nu_protocol::LabeledError;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-// Get UnrealEditor-Cmd.exe path
-pub fn get_unreal_editor_path(unreal_engine_path: &PathBuf) -> PathBuf {
-    unreal_engine_path.join("Engine/Binaries/Win64/UnrealEditor-Cmd.exe")
+// Relative paths to the engine root, in order of preference
+#[cfg(target_os = "windows")]
+const UNREAL_EDITOR_CANDIDATES: &[&str] = &["Engine/Binaries/Win64/UnrealEditor-Cmd.exe"];
+#[cfg(target_os = "macos")]
+const UNREAL_EDITOR_CANDIDATES: &[&str] = &[
+    "Engine/Binaries/Mac/UnrealEditor-Cmd",
+    "Engine/Binaries/Mac/UnrealEditor.app/Contents/MacOS/UnrealEditor",
+];
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const UNREAL_EDITOR_CANDIDATES: &[&str] = &[
+    "Engine/Binaries/Linux/UnrealEditor-Cmd",
+    "Engine/Binaries/Linux/UnrealEditor",
+];
+
+#[cfg(target_os = "windows")]
+const UBT_CANDIDATES: &[&str] = &["Engine/Build/BatchFiles/Build.bat"];
+#[cfg(target_os = "macos")]
+const UBT_CANDIDATES: &[&str] = &["Engine/Build/BatchFiles/Mac/Build.sh"];
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const UBT_CANDIDATES: &[&str] = &["Engine/Build/BatchFiles/Linux/Build.sh"];
+
+#[cfg(target_os = "windows")]
+const UAT_CANDIDATES: &[&str] = &["Engine/Build/BatchFiles/RunUAT.bat"];
+#[cfg(not(target_os = "windows"))]
+const UAT_CANDIDATES: &[&str] = &["Engine/Build/BatchFiles/RunUAT.sh"];
+
+// Get the first candidate existing in the engine
+fn get_existing_engine_path(
+    unreal_engine_path: &PathBuf,
+    candidates: &[&str],
+    name: &str,
+) -> Result<PathBuf, LabeledError> {
+    let candidates: Vec<PathBuf> = candidates
+        .iter()
+        .map(|candidate| unreal_engine_path.join(candidate))
+        .collect();
+    if let Some(path) = candidates.iter().find(|path| path.exists()) {
+        return Ok(path.clone());
+    }
+    Err(
+        LabeledError::new(format!("{} not found", name)).with_help(format!(
+            "Tried: {}",
+            candidates
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    )
 }
 
-pub fn get_unreal_scripts_path(unreal_engine_path: &PathBuf) -> PathBuf {
-    unreal_engine_path.join("Engine/Build/BatchFiles")
+// Get UnrealEditor-Cmd path
+pub fn get_unreal_editor_path(unreal_engine_path: &PathBuf) -> Result<PathBuf, LabeledError> {
+    get_existing_engine_path(unreal_engine_path, UNREAL_EDITOR_CANDIDATES, "UnrealEditor")
 }
 
-// Get Build.bat path
-pub fn get_ubt_path(unreal_engine_path: &PathBuf) -> PathBuf {
-    get_unreal_scripts_path(unreal_engine_path).join("Build.bat")
+// Get Build.bat or Build.sh path
+pub fn get_ubt_path(unreal_engine_path: &PathBuf) -> Result<PathBuf, LabeledError> {
+    get_existing_engine_path(unreal_engine_path, UBT_CANDIDATES, "UnrealBuildTool")
 }
 
-// Get RunUAT.bat path
-pub fn get_uat_path(unreal_engine_path: &PathBuf) -> PathBuf {
-    get_unreal_scripts_path(unreal_engine_path).join("RunUAT.bat")
+// Get RunUAT.bat or RunUAT.sh path
+pub fn get_uat_path(unreal_engine_path: &PathBuf) -> Result<PathBuf, LabeledError> {
+    get_existing_engine_path(unreal_engine_path, UAT_CANDIDATES, "RunUAT")
 }
 
 pub fn get_existing_path(
@@ -52,3 +99,167 @@ pub fn get_unreal_version_selector_path() -> Result<PathBuf, LabeledError> {
         "UnrealVersionSelector not found",
     )
 }
+
+/**
+ * Returns the files under the directory whose name ends with one of the suffixes
+ */
+pub fn find_files(directory: &PathBuf, suffixes: &[&str]) -> Vec<PathBuf> {
+    let mut files = vec![];
+    let mut directories = vec![directory.clone()];
+    while let Some(directory) = directories.pop() {
+        let Ok(entries) = directory.read_dir() else {
+            continue;
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                directories.push(path);
+            } else if let Some(file_name) = path.file_name().and_then(|name| name.to_str()) {
+                if suffixes.iter().any(|suffix| file_name.ends_with(suffix)) {
+                    files.push(path);
+                }
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+// Get the mount point of the content of a directory if it is the root of a plugin, the engine or a project
+fn get_mount_point(root: &Path) -> Option<String> {
+    let root = if root.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        root
+    };
+    let descriptors: Vec<PathBuf> = root
+        .read_dir()
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .collect();
+    if let Some(plugin) = descriptors
+        .iter()
+        .find(|path| path.extension().is_some_and(|ext| ext == "uplugin"))
+    {
+        Some(plugin.file_stem()?.to_string_lossy().to_string())
+    } else if root.file_name().is_some_and(|name| name == "Engine")
+        && root.join("Build/Build.version").exists()
+    {
+        Some("Engine".to_string())
+    } else if descriptors
+        .iter()
+        .any(|path| path.extension().is_some_and(|ext| ext == "uproject"))
+    {
+        Some("Game".to_string())
+    } else {
+        None
+    }
+}
+
+// Get the Content directory of the path and its mount point.
+// A Content folder can contain other Content folders, it is the first one under a project, plugin or engine root.
+fn get_content_directory(path: &Path) -> Option<(PathBuf, String)> {
+    let mut first_content = None;
+    for ancestor in path.ancestors().collect::<Vec<_>>().into_iter().rev() {
+        if ancestor.file_name().is_none_or(|name| name != "Content") {
+            continue;
+        }
+        let root = ancestor.parent().unwrap_or(Path::new(""));
+        if let Some(mount_point) = get_mount_point(root) {
+            return Some((ancestor.to_path_buf(), mount_point));
+        }
+        first_content.get_or_insert(ancestor);
+    }
+    // Without a root, the content is a project content
+    first_content.map(|content| (content.to_path_buf(), "Game".to_string()))
+}
+
+/**
+ * Get the package name of a .uasset or .umap file from its path, e.g. /Game/Meshes/SM_Rock.
+ * The mount point is /Game for the project, /<PluginName> for plugins and /Engine for the engine.
+ */
+pub fn get_package_name(path: &PathBuf) -> Option<String> {
+    let (content_directory, mount_point) = get_content_directory(path)?;
+    let package = path.with_extension("");
+    let components: Vec<String> = package
+        .strip_prefix(&content_directory)
+        .ok()?
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    Some(format!("/{}/{}", mount_point, components.join("/")))
+}
+
+/**
+ * Get the folder of the actors saved with One File Per Actor, e.g. Content/__ExternalActors__/Maps/Main for Content/Maps/Main.umap
+ */
+pub fn get_external_actors_directory(map_path: &PathBuf) -> Option<PathBuf> {
+    let (content_directory, _) = get_content_directory(map_path)?;
+    let map = map_path.with_extension("");
+    Some(
+        content_directory
+            .join("__ExternalActors__")
+            .join(map.strip_prefix(&content_directory).ok()?),
+    )
+}
+
+#[test]
+fn test_get_external_actors_directory() {
+    let content_directory =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/Content");
+    let directory =
+        get_external_actors_directory(&content_directory.join("Maps/Main.umap")).unwrap();
+    assert_eq!(
+        directory,
+        content_directory.join("__ExternalActors__/Maps/Main")
+    );
+    assert_eq!(
+        find_files(&directory, &[".uasset"]),
+        vec![directory.join("0/AB/4TUV9XJ8KZ2M1Q7W3NBC5D.uasset")]
+    );
+
+    // Streaming levels have their own folder
+    assert_eq!(
+        get_external_actors_directory(&content_directory.join("Maps/Main_Sub.umap")).unwrap(),
+        content_directory.join("__ExternalActors__/Maps/Main_Sub")
+    );
+    assert_eq!(
+        get_external_actors_directory(&PathBuf::from("Maps/Main.umap")),
+        None
+    );
+}
+
+#[test]
+fn test_get_package_name() {
+    let directory = std::env::temp_dir().join("nu_plugin_ue_test_get_package_name");
+    let project_directory = directory.join("Content/Vestige");
+    let plugin_directory = project_directory.join("Plugins/Inventory");
+    std::fs::create_dir_all(project_directory.join("Content/Characters/Content")).unwrap();
+    std::fs::create_dir_all(plugin_directory.join("Content/Items")).unwrap();
+    std::fs::write(project_directory.join("Vestige.uproject"), "{}").unwrap();
+    std::fs::write(plugin_directory.join("Inventory.uplugin"), "{}").unwrap();
+
+    // The Content folders above the project and inside its content are not the project content
+    assert_eq!(
+        get_package_name(&project_directory.join("Content/Characters/Content/SK_Hero.uasset")),
+        Some("/Game/Characters/Content/SK_Hero".to_string())
+    );
+    assert_eq!(
+        get_package_name(&plugin_directory.join("Content/Items/Content/DA_Sword.uasset")),
+        Some("/Inventory/Items/Content/DA_Sword".to_string())
+    );
+    assert_eq!(
+        get_external_actors_directory(&project_directory.join("Content/Content/Main.umap")),
+        Some(project_directory.join("Content/__ExternalActors__/Content/Main"))
+    );
+
+    // Without a project, the first Content folder is the project content
+    assert_eq!(
+        get_package_name(&PathBuf::from("Content/Maps/Content/Main.umap")),
+        Some("/Game/Maps/Content/Main".to_string())
+    );
+    assert_eq!(get_package_name(&PathBuf::from("Maps/Main.umap")), None);
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
@@ -1,30 +1,304 @@
-use std::process::{Command, Stdio};
+use std::{
+    collections::VecDeque,
+    io::{BufRead, BufReader, Read},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Instant,
+};
 
-use nu_protocol::{LabeledError, PipelineData, Span, Value};
+use chrono::{DateTime, Local};
+use nu_plugin::{EngineInterface, EvaluatedCall};
+use nu_protocol::{LabeledError, ListStream, PipelineData, Signals, Span, Value, record};
 
+use super::ubt_diagnostics;
+
+// Number of error lines kept to explain a failure
+const ERROR_LINES_COUNT: usize = 10;
+
+// Format the command line like it would be typed in a shell
+fn command_line(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| {
+            let arg = arg.to_string_lossy();
+            if arg.contains(' ') {
+                format!("\"{}\"", arg)
+            } else {
+                arg.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn failed_command_error(
+    command_line: &str,
+    status: ExitStatus,
+    error_lines: &VecDeque<String>,
+    span: Span,
+) -> LabeledError {
+    let status_message = match status.code() {
+        Some(code) => format!("exit code {}", code),
+        None => "terminated by a signal".to_string(),
+    };
+    let mut help = format!("Command: {}", command_line);
+    if !error_lines.is_empty() {
+        help += "\nLast error lines:\n";
+        help += &error_lines.iter().cloned().collect::<Vec<_>>().join("\n");
+    }
+    LabeledError::new(format!("Command failed with {}", status_message))
+        .with_label(status_message, span)
+        .with_help(help)
+}
+
+// UBT and UAT write their errors to stdout
+fn is_error_line(output_line: &OutputLine) -> bool {
+    output_line.stream == OutputStream::Stderr || ubt_diagnostics::is_error_line(&output_line.line)
+}
+
+/**
+ * Run the command, return its output (stdout and stderr lines in order) and fail if the exit status is not a success.
+ * The output is not printed: the stdout of the plugin is the channel of the plugin protocol.
+ */
 pub fn run(command: &mut Command, span: Span) -> Result<PipelineData, LabeledError> {
-    match command
+    let (mut child, receiver) = spawn_lines(command)?;
+
+    let mut output = vec![];
+    let mut error_lines = VecDeque::with_capacity(ERROR_LINES_COUNT);
+    for output_line in receiver {
+        if is_error_line(&output_line) {
+            if error_lines.len() == ERROR_LINES_COUNT {
+                error_lines.pop_front();
+            }
+            error_lines.push_back(output_line.line.clone());
+        }
+        output.push(output_line.line);
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| LabeledError::new(format!("Failed to wait for command: {}", e.to_string())))?;
+    if !status.success() {
+        return Err(failed_command_error(
+            &command_line(command),
+            status,
+            &error_lines,
+            span,
+        ));
+    }
+    Ok(PipelineData::Value(
+        Value::string(output.join("\n"), span),
+        None,
+    ))
+}
+
+/**
+ * Run the command and return a record {exit_code, stdout, stderr, duration} whatever the exit status
+ */
+pub fn run_captured(command: &mut Command, span: Span) -> Result<PipelineData, LabeledError> {
+    let start = Instant::now();
+    let output = command
         .stdin(Stdio::piped())
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| LabeledError::new(format!("Failed to spawn command: {}", e.to_string())))?;
+    let duration = start.elapsed();
+
+    Ok(PipelineData::Value(
+        Value::record(
+            record! {
+                "exit_code" => output.status.code().map(|code| Value::int(code as i64, span)).unwrap_or_default(),
+                "stdout" => Value::string(String::from_utf8_lossy(&output.stdout), span),
+                "stderr" => Value::string(String::from_utf8_lossy(&output.stderr), span),
+                "duration" => Value::duration(duration.as_nanos() as i64, span),
+            },
+            span,
+        ),
+        None,
+    ))
+}
+
+/**
+ * Run the command according to the --stream and --no-fail switches of the call
+ */
+pub fn run_from_call(
+    command: &mut Command,
+    engine: &EngineInterface,
+    call: &EvaluatedCall,
+) -> Result<PipelineData, LabeledError> {
+    let stream = call.has_flag("stream")?;
+    let no_fail = call.has_flag("no-fail")?;
+    if stream {
+        run_streaming(command, call.head, engine.signals(), !no_fail)
+    } else if no_fail {
+        run_captured(command, call.head)
+    } else {
+        run(command, call.head)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl std::fmt::Display for OutputStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputStream::Stdout => write!(f, "stdout"),
+            OutputStream::Stderr => write!(f, "stderr"),
+        }
+    }
+}
+
+pub struct OutputLine {
+    pub line: String,
+    pub stream: OutputStream,
+    pub timestamp: DateTime<Local>,
+}
+
+impl OutputLine {
+    pub fn to_value(&self, span: Span) -> Value {
+        Value::record(
+            record! {
+                "line" => Value::string(&self.line, span),
+                "stream" => Value::string(self.stream.to_string(), span),
+                "timestamp" => Value::date(self.timestamp.fixed_offset(), span),
+            },
+            span,
+        )
+    }
+}
+
+// Send each line of the pipe, UBT and MSVC do not always output UTF-8
+fn forward_lines(
+    pipe: impl Read + Send + 'static,
+    stream: OutputStream,
+    sender: Sender<OutputLine>,
+) {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(pipe);
+        let mut buffer = vec![];
+        while let Ok(size) = reader.read_until(b'\n', &mut buffer) {
+            if size == 0 {
+                break;
+            }
+            let line = String::from_utf8_lossy(&buffer)
+                .trim_end_matches(['\r', '\n'])
+                .to_string();
+            let output_line = OutputLine {
+                line,
+                stream,
+                timestamp: Local::now(),
+            };
+            if sender.send(output_line).is_err() {
+                break;
+            }
+            buffer.clear();
+        }
+    });
+}
+
+/**
+ * Spawn the command and receive the lines of stdout and stderr as they are written.
+ * The command has no input, a prompt (pause in a .bat) reads the end of file instead of waiting forever.
+ */
+pub fn spawn_lines(command: &mut Command) -> Result<(Child, Receiver<OutputLine>), LabeledError> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
         .spawn()
-    {
-        Ok(child) => match child.wait_with_output() {
-            Ok(output) => Ok(PipelineData::Value(
-                Value::String {
-                    val: String::from_utf8_lossy(&output.stdout).to_string(),
-                    internal_span: span,
-                },
-                None,
+        .map_err(|e| LabeledError::new(format!("Failed to spawn command: {}", e.to_string())))?;
+
+    let (sender, receiver) = mpsc::channel();
+    forward_lines(
+        child.stdout.take().unwrap(),
+        OutputStream::Stdout,
+        sender.clone(),
+    );
+    forward_lines(child.stderr.take().unwrap(), OutputStream::Stderr, sender);
+    Ok((child, receiver))
+}
+
+// Stream the values mapped from the output lines, with an error at the end if fail is set and the exit status is not a success
+fn stream_lines(
+    command: &mut Command,
+    span: Span,
+    signals: &Signals,
+    fail: bool,
+    mut map: impl FnMut(&OutputLine) -> Option<Value> + Send + 'static,
+) -> Result<PipelineData, LabeledError> {
+    let (mut child, receiver) = spawn_lines(command)?;
+    let command_line = command_line(command);
+
+    let mut error_lines = VecDeque::with_capacity(ERROR_LINES_COUNT);
+    let mut lines = receiver.into_iter();
+    let mut finished = false;
+    let values = std::iter::from_fn(move || {
+        if finished {
+            return None;
+        }
+        for output_line in lines.by_ref() {
+            let value = map(&output_line);
+            if is_error_line(&output_line) {
+                if error_lines.len() == ERROR_LINES_COUNT {
+                    error_lines.pop_front();
+                }
+                error_lines.push_back(output_line.line);
+            }
+            if value.is_some() {
+                return value;
+            }
+        }
+
+        // Both pipes are closed, reap the process
+        finished = true;
+        match child.wait() {
+            Ok(status) if fail && !status.success() => Some(Value::error(
+                failed_command_error(&command_line, status, &error_lines, span).into(),
+                span,
             )),
-            Err(e) => Err(LabeledError::new(format!(
-                "Failed to wait for command: {}",
-                e.to_string()
-            ))),
-        },
-        Err(e) => Err(LabeledError::new(format!(
-            "Failed to spawn command: {}",
-            e.to_string()
-        ))),
-    }
+            Ok(_) => None,
+            Err(e) => Some(Value::error(
+                LabeledError::new(format!("Failed to wait for command: {}", e.to_string())).into(),
+                span,
+            )),
+        }
+    });
+
+    Ok(PipelineData::ListStream(
+        ListStream::new(values, span, signals.clone()),
+        None,
+    ))
+}
+
+/**
+ * Run the command and stream each output line as a record {line, stream, timestamp}.
+ * If fail is set, an error is yielded at the end of the stream when the exit status is not a success.
+ */
+pub fn run_streaming(
+    command: &mut Command,
+    span: Span,
+    signals: &Signals,
+    fail: bool,
+) -> Result<PipelineData, LabeledError> {
+    stream_lines(command, span, signals, fail, move |output_line| {
+        Some(output_line.to_value(span))
+    })
+}
+
+/**
+ * Run the command and stream the diagnostics of UnrealBuildTool, MSVC and clang found in the output
+ */
+pub fn run_diagnostics(
+    command: &mut Command,
+    span: Span,
+    signals: &Signals,
+    fail: bool,
+) -> Result<PipelineData, LabeledError> {
+    stream_lines(command, span, signals, fail, move |output_line| {
+        ubt_diagnostics::parse_diagnostic(&output_line.line).map(|d| d.to_value(span))
+    })
 }
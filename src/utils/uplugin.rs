@@ -0,0 +1,207 @@
+use std::path::PathBuf;
+
+use nu_protocol::{LabeledError, Span, Value, record};
+
+use super::{
+    asset_scan,
+    descriptor::{
+        ModuleDescriptor, PluginReference, json_string, read_json, read_modules,
+        read_plugin_references,
+    },
+};
+
+// https://dev.epicgames.com/documentation/en-us/unreal-engine/API/Runtime/Projects/FPluginDescriptor
+
+pub struct PluginDescriptor {
+    pub file_version: i64,
+    pub version: i64,
+    pub friendly_name: String,
+    pub version_name: String,
+    pub description: String,
+    pub category: String,
+    pub created_by: String,
+    // Engine version the plugin is compatible with, e.g. 5.4.0, empty for engine plugins
+    pub engine_version: String,
+    pub can_contain_content: bool,
+    pub is_beta_version: bool,
+    pub is_experimental_version: bool,
+    // Set by the Launcher on the plugins it installs
+    pub installed: bool,
+    // None when not set, the default depends on where the plugin is
+    pub enabled_by_default: Option<bool>,
+    pub marketplace_url: Option<String>,
+    pub modules: Vec<ModuleDescriptor>,
+    pub plugins: Vec<PluginReference>,
+}
+
+impl PluginDescriptor {
+    pub fn from_json(object: &serde_json::Value) -> Result<PluginDescriptor, LabeledError> {
+        let json_bool = |key: &str| object.get(key).and_then(|v| v.as_bool());
+        Ok(PluginDescriptor {
+            file_version: object.get("FileVersion").and_then(|v| v.as_i64()).ok_or(
+                LabeledError::new("Plugin descriptor is missing 'FileVersion'"),
+            )?,
+            version: object.get("Version").and_then(|v| v.as_i64()).unwrap_or(0),
+            friendly_name: json_string(object, "FriendlyName").unwrap_or_default(),
+            version_name: json_string(object, "VersionName").unwrap_or_default(),
+            description: json_string(object, "Description").unwrap_or_default(),
+            category: json_string(object, "Category").unwrap_or_default(),
+            created_by: json_string(object, "CreatedBy").unwrap_or_default(),
+            engine_version: json_string(object, "EngineVersion").unwrap_or_default(),
+            can_contain_content: json_bool("CanContainContent").unwrap_or(false),
+            is_beta_version: json_bool("IsBetaVersion").unwrap_or(false),
+            is_experimental_version: json_bool("IsExperimentalVersion").unwrap_or(false),
+            installed: json_bool("Installed").unwrap_or(false),
+            enabled_by_default: json_bool("EnabledByDefault"),
+            marketplace_url: json_string(object, "MarketplaceURL"),
+            modules: read_modules(object)?,
+            plugins: read_plugin_references(object)?,
+        })
+    }
+
+    pub fn to_value(&self, span: Span) -> Value {
+        Value::record(
+            record! {
+                "file_version" => Value::int(self.file_version, span),
+                "version" => Value::int(self.version, span),
+                "version_name" => Value::string(&self.version_name, span),
+                "friendly_name" => Value::string(&self.friendly_name, span),
+                "description" => Value::string(&self.description, span),
+                "category" => Value::string(&self.category, span),
+                "created_by" => Value::string(&self.created_by, span),
+                "engine_version" => Value::string(&self.engine_version, span),
+                "can_contain_content" => Value::bool(self.can_contain_content, span),
+                "is_beta_version" => Value::bool(self.is_beta_version, span),
+                "is_experimental_version" => Value::bool(self.is_experimental_version, span),
+                "installed" => Value::bool(self.installed, span),
+                "enabled_by_default" => self.enabled_by_default.map(|e| Value::bool(e, span)).unwrap_or_default(),
+                "marketplace_url" => self.marketplace_url.as_ref().map(|u| Value::string(u, span)).unwrap_or_default(),
+                "modules" => Value::list(self.modules.iter().map(|m| m.to_value(span)).collect(), span),
+                "plugins" => Value::list(self.plugins.iter().map(|p| p.to_value(span)).collect(), span),
+            },
+            span,
+        )
+    }
+
+    pub fn from_path(uplugin_path: &PathBuf) -> Result<PluginDescriptor, LabeledError> {
+        PluginDescriptor::from_json(&read_json(uplugin_path)?).map_err(|e| {
+            LabeledError::new(format!("Invalid {}: {}", uplugin_path.display(), e.msg))
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PluginSource {
+    Engine,
+    // Installed from the Marketplace or Fab in Engine/Plugins/Marketplace
+    Marketplace,
+    Project,
+}
+
+impl std::fmt::Display for PluginSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PluginSource::Engine => write!(f, "Engine"),
+            PluginSource::Marketplace => write!(f, "Marketplace"),
+            PluginSource::Project => write!(f, "Project"),
+        }
+    }
+}
+
+/**
+ * Plugin found in the engine or the project, its name is the name of the .uplugin file
+ */
+pub struct Plugin {
+    pub name: String,
+    pub uplugin_path: PathBuf,
+    pub source: PluginSource,
+    pub descriptor: PluginDescriptor,
+}
+
+impl Plugin {
+    /**
+     * Engine plugins are disabled by default, project plugins are enabled by default
+     */
+    pub fn is_enabled_by_default(&self) -> bool {
+        self.descriptor
+            .enabled_by_default
+            .unwrap_or(self.source == PluginSource::Project)
+    }
+}
+
+/**
+ * Returns the .uplugin files under the directory, without looking into the plugins like the PluginManager
+ */
+pub fn find_uplugin_files(directory: &PathBuf) -> Vec<PathBuf> {
+    let mut files = vec![];
+    let mut directories = vec![directory.clone()];
+    while let Some(directory) = directories.pop() {
+        let Ok(entries) = directory.read_dir() else {
+            continue;
+        };
+        let paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect();
+        let uplugin_files: Vec<&PathBuf> = paths
+            .iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == "uplugin"))
+            .collect();
+        if uplugin_files.is_empty() {
+            directories.extend(paths.into_iter().filter(|path| path.is_dir()));
+        } else {
+            files.extend(uplugin_files.into_iter().cloned());
+        }
+    }
+    files.sort();
+    files
+}
+
+/**
+ * Returns the plugins of the engine and the project, plugins that cannot be read are skipped with a warning
+ */
+pub fn find_plugins(unreal_engine_path: Option<&PathBuf>, project_dir: &PathBuf) -> Vec<Plugin> {
+    let mut uplugin_files = vec![];
+    if let Some(unreal_engine_path) = unreal_engine_path {
+        let marketplace_dir = unreal_engine_path.join("Engine/Plugins/Marketplace");
+        uplugin_files.extend(
+            find_uplugin_files(&unreal_engine_path.join("Engine/Plugins"))
+                .into_iter()
+                .map(|path| {
+                    let source = if path.starts_with(&marketplace_dir) {
+                        PluginSource::Marketplace
+                    } else {
+                        PluginSource::Engine
+                    };
+                    (path, source)
+                }),
+        );
+    }
+    uplugin_files.extend(
+        find_uplugin_files(&project_dir.join("Plugins"))
+            .into_iter()
+            .map(|path| (path, PluginSource::Project)),
+    );
+
+    uplugin_files
+        .into_iter()
+        .filter_map(
+            |(uplugin_path, source)| match PluginDescriptor::from_path(&uplugin_path) {
+                Ok(descriptor) => Some(Plugin {
+                    name: uplugin_path
+                        .file_stem()
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string(),
+                    uplugin_path,
+                    source,
+                    descriptor,
+                }),
+                Err(e) => {
+                    asset_scan::warn_skipped_package(&e);
+                    None
+                }
+            },
+        )
+        .collect()
+}
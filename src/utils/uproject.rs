@@ -4,6 +4,12 @@ use nu_plugin::EngineInterface;
 use nu_protocol::LabeledError;
 use regex::Regex;
 
+use super::descriptor::{
+    ModuleDescriptor, PluginReference, json_string, json_string_list, read_json, read_modules,
+    read_plugin_references,
+};
+use super::ue_engines::{self, EngineSource};
+
 /**
  * Returns the path to the .uproject file in the current directory
  */
@@ -55,89 +61,286 @@ impl std::fmt::Display for IDE {
     }
 }
 
+// https://dev.epicgames.com/documentation/en-us/unreal-engine/API/Runtime/Projects/FProjectDescriptor
+
+pub struct ProjectDescriptor {
+    pub file_version: i64,
+    pub engine_association: String,
+    pub category: String,
+    pub description: String,
+    pub modules: Vec<ModuleDescriptor>,
+    pub plugins: Vec<PluginReference>,
+    pub target_platforms: Vec<String>,
+}
+
+impl ProjectDescriptor {
+    pub fn from_json(object: &serde_json::Value) -> Result<ProjectDescriptor, LabeledError> {
+        Ok(ProjectDescriptor {
+            file_version: object.get("FileVersion").and_then(|v| v.as_i64()).ok_or(
+                LabeledError::new("Project descriptor is missing 'FileVersion'"),
+            )?,
+            engine_association: json_string(object, "EngineAssociation").unwrap_or_default(),
+            category: json_string(object, "Category").unwrap_or_default(),
+            description: json_string(object, "Description").unwrap_or_default(),
+            modules: read_modules(object)?,
+            plugins: read_plugin_references(object)?,
+            target_platforms: json_string_list(object, "TargetPlatforms"),
+        })
+    }
+
+    pub fn from_path(uproject_path: &PathBuf) -> Result<ProjectDescriptor, LabeledError> {
+        ProjectDescriptor::from_json(&read_json(uproject_path)?).map_err(|e| {
+            LabeledError::new(format!("Invalid {}: {}", uproject_path.display(), e.msg))
+        })
+    }
+}
+
+/**
+ * Enable or disable a plugin in the Plugins of a .uproject, the other fields are kept in order
+ */
+pub fn set_plugin_enabled(
+    uproject_path: &PathBuf,
+    name: &str,
+    enabled: bool,
+    marketplace_url: Option<&str>,
+) -> Result<(), LabeledError> {
+    let content = std::fs::read_to_string(uproject_path).map_err(|e| {
+        LabeledError::new(format!("Failed to read {}: {}", uproject_path.display(), e))
+    })?;
+    let content =
+        set_plugin_enabled_in_content(&content, name, enabled, marketplace_url).map_err(|e| {
+            LabeledError::new(format!("Invalid {}: {}", uproject_path.display(), e.msg))
+        })?;
+    std::fs::write(uproject_path, content).map_err(|e| {
+        LabeledError::new(format!(
+            "Failed to write {}: {}",
+            uproject_path.display(),
+            e
+        ))
+    })
+}
+
+/**
+ * Enable or disable a plugin in the content of a .uproject.
+ * The BOM, line endings, indentation and trailing newline of the content are kept.
+ */
+pub fn set_plugin_enabled_in_content(
+    content: &str,
+    name: &str,
+    enabled: bool,
+    marketplace_url: Option<&str>,
+) -> Result<String, LabeledError> {
+    let (bom, content) = match content.strip_prefix('\u{feff}') {
+        Some(content) => ("\u{feff}", content),
+        None => ("", content),
+    };
+    let mut json: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| LabeledError::new(e.to_string()))?;
+    let object = json
+        .as_object_mut()
+        .ok_or(LabeledError::new("not a JSON object"))?;
+    let plugins = object
+        .entry("Plugins")
+        .or_insert_with(|| serde_json::Value::Array(vec![]))
+        .as_array_mut()
+        .ok_or(LabeledError::new("Plugins is not an array"))?;
+
+    let reference = plugins.iter_mut().find(|plugin| {
+        json_string(plugin, "Name")
+            .is_some_and(|plugin_name| plugin_name.eq_ignore_ascii_case(name))
+    });
+    match reference {
+        Some(reference) => {
+            reference["Enabled"] = serde_json::Value::Bool(enabled);
+        }
+        None => {
+            let mut reference = serde_json::Map::new();
+            reference.insert("Name".to_string(), name.into());
+            reference.insert("Enabled".to_string(), enabled.into());
+            if let Some(marketplace_url) = marketplace_url {
+                reference.insert("MarketplaceURL".to_string(), marketplace_url.into());
+            }
+            plugins.push(serde_json::Value::Object(reference));
+        }
+    }
+
+    // The editor saves the descriptors indented with tabs, files edited by hand can use spaces
+    let indent = content
+        .lines()
+        .find_map(|line| {
+            let trimmed = line.trim_start();
+            (!trimmed.is_empty() && trimmed.len() < line.len())
+                .then(|| line[..line.len() - trimmed.len()].to_string())
+        })
+        .unwrap_or("\t".to_string());
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    // Strings are escaped on a single line, the leading spaces are the indentation of serde_json
+    let lines: Vec<String> = serde_json::to_string_pretty(&json)
+        .map_err(|e| LabeledError::new(e.to_string()))?
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start_matches(' ');
+            indent.repeat((line.len() - trimmed.len()) / 2) + trimmed
+        })
+        .collect();
+    let trailing_newline = if content.ends_with('\n') { newline } else { "" };
+    Ok(format!(
+        "{}{}{}",
+        bom,
+        lines.join(newline),
+        trailing_newline
+    ))
+}
+
 pub struct UProject {
     pub uproject_path: PathBuf,
     pub name: String,
     pub ide: IDE,
     pub unreal_engine_path: Option<PathBuf>,
+    pub unreal_engine_source: Option<EngineSource>,
+    // Read from the JSON even when the descriptor is invalid
+    pub engine_association: String,
+    // The commands not using the descriptor still work with an invalid .uproject
+    pub descriptor: Result<ProjectDescriptor, LabeledError>,
 }
 
-// Assume uproject_path is valid
-// Get the Engine path of a project
-fn get_unreal_engine_path(uproject: &UProject) -> Result<Option<PathBuf>, LabeledError> {
-    // Saved/Config/WindowsEditor/EditorPerProjectUserSettings.ini contains Directories2.Project, but not updated when moving UE
-    // Intermediate/PipInstall/Lib/site-packages/plugin_site_package.pth is updated with any IDE
-    const PIP_INSTALL_SITE_PACKAGE: &str =
-        "Intermediate/PipInstall/Lib/site-packages/plugin_site_package.pth";
-    let pip_install_site_package = uproject
-        .uproject_path
-        .parent()
-        .unwrap()
-        .join(PIP_INSTALL_SITE_PACKAGE);
+// Get the Engine path from EngineAssociation, the same way UnrealVersionSelector does
+fn get_unreal_engine_path_from_association(
+    uproject: &UProject,
+) -> Result<Option<(PathBuf, EngineSource)>, LabeledError> {
+    let project_dir = uproject.uproject_path.parent().unwrap();
+    let association = &uproject.engine_association;
+
+    if association.is_empty() {
+        // A project without association is inside the engine tree (native project)
+        return Ok(project_dir
+            .ancestors()
+            .map(|dir| dir.to_path_buf())
+            .find(ue_engines::is_engine_root)
+            .map(|path| (path, EngineSource::ParentDirectory)));
+    }
+
+    let installation = ue_engines::get_launcher_installations()?
+        .into_iter()
+        .chain(ue_engines::get_registered_installations()?)
+        .find(|installation| ue_engines::is_same_identifier(&installation.identifier, association));
+    if let Some(installation) = installation {
+        return Ok(Some((installation.path, installation.source)));
+    }
+
+    // Source builds next to the project can be referenced with a relative path
+    let relative_path = project_dir.join(association);
+    if ue_engines::is_engine_root(&relative_path) {
+        return Ok(Some((relative_path, EngineSource::RelativePath)));
+    }
+
+    Ok(None)
+}
+
+// Get the Engine path from the UnrealBuildTool project of the .sln
+fn get_unreal_engine_path_from_sln(uproject: &UProject) -> Result<Option<PathBuf>, LabeledError> {
     let sln_path = uproject
         .uproject_path
         .parent()
         .unwrap()
         .join(uproject.name.to_owned() + ".sln");
+    if !sln_path.exists() {
+        return Ok(None);
+    }
 
-    let engine_path = if sln_path.exists() {
-        // open Vestige.sln | parse --regex "Project.+\"UnrealBuildTool\", *(.+), .+" | get capture0.0
-        let content = std::fs::read_to_string(&sln_path).map_err(|e| {
-            LabeledError::new(format!(
-                "Failed to read {}: {}",
-                sln_path.display(),
-                e.to_string()
-            ))
-        })?;
-        let re = Regex::new(r#"Project.+"UnrealBuildTool", *\"(.+)Engine[\\/]Source[\\/]Programs[\\/]UnrealBuildTool[\\/]UnrealBuildTool.csproj\", .+"#).unwrap();
-
-        let first_match: Option<String> = re
-            .captures_iter(&content)
-            .map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
-            .flatten()
-            .next();
+    // open Vestige.sln | parse --regex "Project.+\"UnrealBuildTool\", *(.+), .+" | get capture0.0
+    let content = std::fs::read_to_string(&sln_path).map_err(|e| {
+        LabeledError::new(format!(
+            "Failed to read {}: {}",
+            sln_path.display(),
+            e.to_string()
+        ))
+    })?;
+    let re = Regex::new(r#"Project.+"UnrealBuildTool", *\"(.+)Engine[\\/]Source[\\/]Programs[\\/]UnrealBuildTool[\\/]UnrealBuildTool.csproj\", .+"#).unwrap();
 
-        if first_match.is_none() {
-            return Err(LabeledError::new(format!(
-                "Failed to find Engine path in {}",
-                sln_path.display()
-            )));
-        }
+    let first_match: Option<String> = re
+        .captures_iter(&content)
+        .map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
+        .flatten()
+        .next();
 
-        let engine_relative_path = PathBuf::from(&first_match.unwrap());
+    // Solutions generated without the engine projects fall through to the next source
+    let Some(first_match) = first_match else {
+        return Ok(None);
+    };
+    let engine_relative_path = PathBuf::from(&first_match);
 
+    Ok(Some(
         uproject
             .uproject_path
             .parent()
             .unwrap()
-            .join(engine_relative_path)
-    } else if pip_install_site_package.exists() {
-        let content = std::fs::read_to_string(pip_install_site_package).map_err(|e| {
-            LabeledError::new(format!(
-                "Failed to read {}: {}",
-                PIP_INSTALL_SITE_PACKAGE,
-                e.to_string()
-            ))
-        })?;
+            .join(engine_relative_path),
+    ))
+}
+
+// Saved/Config/WindowsEditor/EditorPerProjectUserSettings.ini contains Directories2.Project, but not updated when moving UE
+// Intermediate/PipInstall/Lib/site-packages/plugin_site_package.pth is updated with any IDE
+const PIP_INSTALL_SITE_PACKAGE: &str =
+    "Intermediate/PipInstall/Lib/site-packages/plugin_site_package.pth";
+
+// Get the Engine path from the site-packages of the Python plugin
+fn get_unreal_engine_path_from_pip_install(
+    uproject: &UProject,
+) -> Result<Option<PathBuf>, LabeledError> {
+    let pip_install_site_package = uproject
+        .uproject_path
+        .parent()
+        .unwrap()
+        .join(PIP_INSTALL_SITE_PACKAGE);
+    if !pip_install_site_package.exists() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(pip_install_site_package).map_err(|e| {
+        LabeledError::new(format!(
+            "Failed to read {}: {}",
+            PIP_INSTALL_SITE_PACKAGE,
+            e.to_string()
+        ))
+    })?;
+
+    const CONTENT_END_PATH: &str =
+        "Engine/Plugins/Runtime/USDCore/Content/Python/Lib/Win64/site-packages";
+    let content_split = content.split(CONTENT_END_PATH).collect::<Vec<&str>>();
+    if content_split.len() != 2 {
+        return Ok(None);
+    }
+    Ok(Some(PathBuf::from(content_split[0])))
+}
 
-        const CONTENT_END_PATH: &str =
-            "Engine/Plugins/Runtime/USDCore/Content/Python/Lib/Win64/site-packages";
-        let content_split = content.split(CONTENT_END_PATH).collect::<Vec<&str>>();
-        assert!(content_split.len() == 2);
-        PathBuf::from(content_split[0])
+// Assume uproject_path is valid
+// Get the Engine path of a project and where it was found
+fn get_unreal_engine_path(
+    uproject: &UProject,
+) -> Result<Option<(PathBuf, EngineSource)>, LabeledError> {
+    let engine = if let Some(engine) = get_unreal_engine_path_from_association(uproject)? {
+        engine
+    } else if let Some(path) = get_unreal_engine_path_from_sln(uproject)? {
+        (path, EngineSource::Solution)
+    } else if let Some(path) = get_unreal_engine_path_from_pip_install(uproject)? {
+        (path, EngineSource::PipInstall)
     } else {
-        println!(
-            "Warning: Failed to find {} or {} to get Engine path",
-            sln_path.display(),
-            PIP_INSTALL_SITE_PACKAGE
+        eprintln!(
+            "Warning: Failed to resolve EngineAssociation '{}', {}.sln or {} to get Engine path",
+            uproject.engine_association, uproject.name, PIP_INSTALL_SITE_PACKAGE
         );
         return Ok(None);
     };
 
-    if !engine_path.exists() {
-        println!("Warning: Engine {} does not exist", engine_path.display());
+    if !engine.0.exists() {
+        eprintln!("Warning: Engine {} does not exist", engine.0.display());
     }
-    return Ok(Some(engine_path));
+    return Ok(Some(engine));
 }
 
 impl UProject {
@@ -177,13 +380,26 @@ impl UProject {
             name: uproject_name.to_string(),
             ide,
             unreal_engine_path: None,
+            unreal_engine_source: None,
+            engine_association: read_json(uproject_path)
+                .ok()
+                .and_then(|json| json_string(&json, "EngineAssociation"))
+                .unwrap_or_default(),
+            descriptor: ProjectDescriptor::from_path(uproject_path),
         };
 
-        uproject.unreal_engine_path = get_unreal_engine_path(&uproject)?;
+        if let Some((path, source)) = get_unreal_engine_path(&uproject)? {
+            uproject.unreal_engine_path = Some(path);
+            uproject.unreal_engine_source = Some(source);
+        }
 
         return Ok(uproject);
     }
 
+    pub fn get_descriptor(&self) -> Result<&ProjectDescriptor, LabeledError> {
+        self.descriptor.as_ref().map_err(|e| e.clone())
+    }
+
     pub fn get_unreal_engine_path_str(&self) -> Result<&PathBuf, LabeledError> {
         match &self.unreal_engine_path {
             Some(path) => Ok(&path),
@@ -193,3 +409,33 @@ impl UProject {
         }
     }
 }
+
+#[test]
+fn test_set_plugin_enabled() {
+    let content = "{\r\n    \"FileVersion\": 3,\r\n    \"Plugins\": [\r\n        {\r\n            \"Name\": \"ModelingToolsEditorMode\",\r\n            \"Enabled\": true,\r\n            \"TargetAllowList\": [\r\n                \"Editor\"\r\n            ]\r\n        }\r\n    ]\r\n}\r\n";
+
+    // Only the Enabled field changes
+    let edited =
+        set_plugin_enabled_in_content(content, "modelingtoolseditormode", false, None).unwrap();
+    assert_eq!(
+        edited,
+        content.replace("\"Enabled\": true", "\"Enabled\": false")
+    );
+
+    let edited = set_plugin_enabled_in_content(
+        content,
+        "Bridge",
+        true,
+        Some("com.epicgames.launcher://ue/Fab/product/bridge"),
+    )
+    .unwrap();
+    assert!(edited.ends_with("        },\r\n        {\r\n            \"Name\": \"Bridge\",\r\n            \"Enabled\": true,\r\n            \"MarketplaceURL\": \"com.epicgames.launcher://ue/Fab/product/bridge\"\r\n        }\r\n    ]\r\n}\r\n"));
+
+    // Tabs and no trailing newline, the Plugins are added after the other fields
+    let content = "\u{feff}{\n\t\"FileVersion\": 3,\n\t\"EngineAssociation\": \"5.4\"\n}";
+    let edited = set_plugin_enabled_in_content(content, "Bridge", false, None).unwrap();
+    assert_eq!(
+        edited,
+        "\u{feff}{\n\t\"FileVersion\": 3,\n\t\"EngineAssociation\": \"5.4\",\n\t\"Plugins\": [\n\t\t{\n\t\t\t\"Name\": \"Bridge\",\n\t\t\t\"Enabled\": false\n\t\t}\n\t]\n}"
+    );
+}
@@ -0,0 +1,411 @@
+"""
+Generate the package fixtures of tests/fixtures/Content.
+
+The packages follow the layout of FPackageFileSummary, the name, import and export maps,
+the soft package references, the soft object paths and the asset registry data:
+https://github.com/EpicGames/UnrealEngine/blob/release/Engine/Source/Runtime/CoreUObject/Private/UObject/PackageFileSummary.cpp
+https://github.com/EpicGames/UnrealEngine/blob/release/Engine/Source/Runtime/CoreUObject/Private/UObject/ObjectResource.cpp
+
+Usage: python tests/fixtures/generate_packages.py
+"""
+
+import os
+import struct
+
+PACKAGE_FILE_TAG = 0x9E2A83C1
+
+# EPackageFlags
+PKG_COOKED = 0x00000200
+PKG_FILTER_EDITOR_ONLY = 0x80000000
+
+# EObjectFlags
+RF_PUBLIC = 0x1
+RF_STANDALONE = 0x2
+RF_TRANSACTIONAL = 0x8
+RF_CLASS_DEFAULT_OBJECT = 0x10
+RF_ARCHETYPE_OBJECT = 0x20
+
+UE4_27 = (-7, 522, None, (4, 27, 2, 18319896, "++UE4+Release-4.27"))
+UE5_0 = (-8, 522, 1004, (5, 0, 3, 20979098, "++UE5+Release-5.0"))
+UE5_4 = (-8, 522, 1012, (5, 4, 4, 35576357, "++UE5+Release-5.4"))
+
+
+def i32(value):
+    return struct.pack("<i", value)
+
+
+def u32(value):
+    return struct.pack("<I", value)
+
+
+def i64(value):
+    return struct.pack("<q", value)
+
+
+def fstring(value):
+    # The length includes the null terminator, negative for UTF-16
+    if not value:
+        return i32(0)
+    if value.isascii():
+        data = value.encode("ascii") + b"\0"
+        return i32(len(data)) + data
+    data = value.encode("utf-16-le") + b"\0\0"
+    return i32(-(len(data) // 2)) + data
+
+
+def guid(seed):
+    return bytes((seed + i) % 256 for i in range(16))
+
+
+class Package:
+    def __init__(self, version, package_flags=0):
+        self.legacy_version, self.file_version, self.file_version_ue5, self.engine_version = version
+        self.package_flags = package_flags
+        self.names = []
+        self.imports = []
+        self.exports = []
+        self.soft_package_references = []
+        self.soft_object_paths = []
+        self.registry = []
+        self.name("None")
+
+    @property
+    def filter_editor_only(self):
+        return self.package_flags & PKG_FILTER_EDITOR_ONLY != 0
+
+    def ue5(self, version):
+        return self.file_version_ue5 is not None and self.file_version_ue5 >= version
+
+    # FName: index in the name map and number, the suffix _N is saved as N + 1
+    def name(self, value):
+        number = 0
+        base, _, suffix = value.rpartition("_")
+        if base and suffix.isdigit() and (suffix == "0" or not suffix.startswith("0")):
+            value, number = base, int(suffix) + 1
+        if value not in self.names:
+            self.names.append(value)
+        return i32(self.names.index(value)) + i32(number)
+
+    def add_import(self, class_package, class_name, outer, object_name):
+        self.imports.append((class_package, class_name, outer, object_name))
+        return -len(self.imports)
+
+    def add_export(self, class_index, outer, object_name, template=0, is_asset=False, data=b"\0" * 4, flags=None):
+        if flags is None:
+            flags = RF_PUBLIC | RF_STANDALONE | RF_TRANSACTIONAL if is_asset else RF_TRANSACTIONAL
+        self.exports.append(
+            {
+                "class": class_index,
+                "super": 0,
+                "template": template,
+                "outer": outer,
+                "name": object_name,
+                "flags": flags,
+                "is_asset": is_asset,
+                "data": data,
+            }
+        )
+        return len(self.exports)
+
+    def write_summary(self, offsets):
+        out = u32(PACKAGE_FILE_TAG) + i32(self.legacy_version) + i32(864)
+        out += i32(self.file_version)
+        if self.legacy_version <= -8:
+            out += i32(self.file_version_ue5)
+        out += i32(0)  # FileVersionLicenseeUE4
+        out += i32(0)  # CustomVersions
+        out += i32(offsets["total_header_size"])
+        out += fstring("None")
+        out += u32(self.package_flags)
+        out += i32(len(self.names)) + i32(offsets["names"])
+        if self.ue5(1008):
+            out += i32(len(self.soft_object_paths)) + i32(offsets["soft_object_paths"])
+        if not self.filter_editor_only:
+            out += fstring("")  # LocalizationId
+        out += i32(0) + i32(0)  # GatherableTextData
+        out += i32(len(self.exports)) + i32(offsets["exports"])
+        out += i32(len(self.imports)) + i32(offsets["imports"])
+        out += i32(offsets["depends"])
+        out += i32(len(self.soft_package_references)) + i32(offsets["soft_package_references"])
+        out += i32(0)  # SearchableNamesOffset
+        out += i32(0)  # ThumbnailTableOffset
+        out += guid(1)
+        if not self.filter_editor_only:
+            out += guid(2)  # PersistentGuid
+        out += i32(1) + i32(len(self.exports)) + i32(len(self.names))  # Generations
+        major, minor, patch, changelist, branch = self.engine_version
+        engine_version = struct.pack("<HHHI", major, minor, patch, changelist) + fstring(branch)
+        out += engine_version  # SavedByEngineVersion
+        out += engine_version  # CompatibleWithEngineVersion
+        out += u32(0)  # CompressionFlags
+        out += i32(0)  # CompressedChunks
+        out += u32(0)  # PackageSource
+        out += i32(0)  # AdditionalPackagesToCook
+        if self.legacy_version > -7:
+            out += i32(0)  # NumTextureAllocations
+        out += i32(offsets["asset_registry"])
+        out += i64(offsets["bulk_data"])
+        out += i32(0)  # WorldTileInfoDataOffset
+        out += i32(0)  # ChunkIDs
+        out += i32(0) + i32(0)  # PreloadDependency
+        if self.ue5(1001):
+            out += i32(len(self.names))  # NamesReferencedFromExportDataCount
+        if self.ue5(1002):
+            out += i64(-1)  # PayloadTocOffset
+        if self.ue5(1009):
+            out += i32(-1)  # DataResourceOffset
+        return out
+
+    def write_names(self):
+        # Each name is followed by its hashes
+        return b"".join(fstring(name) + u32(0) for name in self.names)
+
+    def write_soft_object_paths(self):
+        if not self.ue5(1008):
+            return b""
+        return b"".join(
+            self.name(package) + self.name(asset) + fstring(sub_path)
+            for package, asset, sub_path in self.soft_object_paths
+        )
+
+    def write_imports(self):
+        out = b""
+        for class_package, class_name, outer, object_name in self.imports:
+            out += self.name(class_package) + self.name(class_name) + i32(outer) + self.name(object_name)
+            if not self.filter_editor_only:
+                out += self.name("None")  # PackageName
+            if self.ue5(1003):
+                out += i32(0)  # bImportOptional
+        return out
+
+    def write_exports(self, data_offset):
+        out = b""
+        for export in self.exports:
+            out += i32(export["class"]) + i32(export["super"]) + i32(export["template"]) + i32(export["outer"])
+            out += self.name(export["name"])
+            out += u32(export["flags"])
+            out += i64(len(export["data"])) + i64(data_offset)
+            data_offset += len(export["data"])
+            out += i32(0) + i32(0) + i32(0)  # bForcedExport, bNotForClient, bNotForServer
+            if not self.ue5(1005):
+                out += guid(3)  # PackageGuid
+            if self.ue5(1006):
+                out += i32(0)  # bIsInheritedInstance
+            out += u32(0)  # PackageFlags
+            out += i32(0)  # bNotAlwaysLoadedForEditorGame
+            out += i32(1 if export["is_asset"] else 0)
+            if self.ue5(1003):
+                out += i32(0)  # bGeneratePublicHash
+            out += i32(-1) + i32(0) * 4  # FirstExportDependency and the dependency counts
+            if self.ue5(1010):
+                out += i64(0) + i64(0)  # ScriptSerializationStartOffset and ScriptSerializationEndOffset
+        return out
+
+    def write_soft_package_references(self):
+        return b"".join(self.name(package) for package in self.soft_package_references)
+
+    def write_asset_registry(self, offset):
+        objects = i32(len(self.registry))
+        for object_path, class_name, tags in self.registry:
+            objects += fstring(object_path) + fstring(class_name) + i32(len(tags))
+            objects += b"".join(fstring(key) + fstring(value) for key, value in tags)
+        if self.filter_editor_only:
+            return objects
+        # DependencyDataOffset, followed by the empty dependency data
+        return i64(offset + 8 + len(objects)) + objects + i32(0) + i32(0)
+
+    def build(self):
+        # The names are registered while writing the tables, write them once before the layout
+        self.write_soft_object_paths()
+        self.write_imports()
+        self.write_exports(0)
+        self.write_soft_package_references()
+
+        offsets = {key: 0 for key in [
+            "total_header_size", "names", "soft_object_paths", "imports", "exports", "depends",
+            "soft_package_references", "asset_registry", "bulk_data",
+        ]}
+        position = len(self.write_summary(offsets))
+        sections = [
+            ("names", lambda: self.write_names()),
+            ("soft_object_paths", lambda: self.write_soft_object_paths()),
+            ("imports", lambda: self.write_imports()),
+            ("exports", lambda: self.write_exports(0)),
+            ("depends", lambda: i32(0) * len(self.exports)),
+            ("soft_package_references", lambda: self.write_soft_package_references()),
+            ("asset_registry", lambda: self.write_asset_registry(offsets["asset_registry"])),
+        ]
+        for key, write in sections:
+            offsets[key] = position
+            position += len(write())
+        offsets["total_header_size"] = position
+
+        export_data = b"".join(export["data"] for export in self.exports)
+        offsets["bulk_data"] = position + len(export_data)
+
+        out = self.write_summary(offsets)
+        for key, write in sections:
+            assert len(out) == offsets[key], key
+            out += write() if key != "exports" else self.write_exports(position)
+        return out + export_data + u32(PACKAGE_FILE_TAG)
+
+
+def save(path, package):
+    path = os.path.join(os.path.dirname(__file__), "Content", path)
+    os.makedirs(os.path.dirname(path), exist_ok=True)
+    with open(path, "wb") as file:
+        file.write(package.build())
+
+
+def static_mesh():
+    package = Package(UE5_4)
+    engine = package.add_import("/Script/CoreUObject", "Package", 0, "/Script/Engine")
+    static_mesh = package.add_import("/Script/CoreUObject", "Class", engine, "StaticMesh")
+    body_setup = package.add_import("/Script/CoreUObject", "Class", engine, "BodySetup")
+    material_package = package.add_import("/Script/CoreUObject", "Package", 0, "/Game/Materials/M_Rock")
+    package.add_import("/Script/Engine", "Material", material_package, "M_Rock")
+    template = package.add_import("/Script/Engine", "StaticMesh", engine, "Default__StaticMesh")
+    mesh = package.add_export(static_mesh, 0, "SM_Rock", template=template, is_asset=True, data=b"\1" * 16)
+    package.add_export(body_setup, mesh, "BodySetup_0")
+    package.add_export(body_setup, mesh, "Géométrie")
+    package.soft_package_references = ["/Game/Textures/T_Rock"]
+    package.soft_object_paths = [
+        ("/Game/Maps/Main", "Main", "PersistentLevel.Lumière_Rocher"),
+        ("/Game/Textures/T_Rock", "T_Rock", ""),
+    ]
+    # UE 5 saves the object path without the package name
+    package.registry = [
+        (
+            "SM_Rock",
+            "/Script/Engine.StaticMesh",
+            [("NumTriangles", "120"), ("AssetImportData", '[{ "RelativeFilename" : "Rocher_é.fbx" }]')],
+        )
+    ]
+    return package
+
+
+def blueprint():
+    # Cooked in UE 5.0: editor only data is filtered out and soft object paths are not listed
+    package = Package(UE5_0, PKG_COOKED | PKG_FILTER_EDITOR_ONLY)
+    engine = package.add_import("/Script/CoreUObject", "Package", 0, "/Script/Engine")
+    generated_class = package.add_import("/Script/CoreUObject", "Class", engine, "BlueprintGeneratedClass")
+    actor_template = package.add_import("/Script/Engine", "Actor", engine, "Default__Actor")
+    door_class = package.add_export(generated_class, 0, "BP_Door_C", is_asset=True)
+    package.add_export(door_class, 0, "Default__BP_Door_C", template=actor_template)
+    package.soft_package_references = ["/Game/Sounds/S_Door"]
+    # Not saved before UE 5.1
+    package.soft_object_paths = [("/Game/Maps/Main", "Main", "")]
+    package.registry = [
+        (
+            "/Game/Blueprints/BP_Door.BP_Door_C",
+            "/Script/Engine.BlueprintGeneratedClass",
+            [("ParentClass", "/Script/CoreUObject.Class'/Script/Engine.Actor'")],
+        )
+    ]
+    return package
+
+
+def legacy_static_mesh():
+    package = Package(UE4_27)
+    engine = package.add_import("/Script/CoreUObject", "Package", 0, "/Script/Engine")
+    static_mesh = package.add_import("/Script/CoreUObject", "Class", engine, "StaticMesh")
+    body_setup = package.add_import("/Script/CoreUObject", "Class", engine, "BodySetup")
+    mesh = package.add_export(static_mesh, 0, "SM_Crate", is_asset=True, data=b"\1" * 8)
+    package.add_export(body_setup, mesh, "BodySetup_0")
+    return package
+
+
+def redirector(object_name, destination_package, destination_class):
+    # Left by a rename, UObjectRedirector::Serialize writes the DestinationObject after the properties of UObject
+    package = Package(UE5_4)
+    core = package.add_import("/Script/CoreUObject", "Package", 0, "/Script/CoreUObject")
+    redirector_class = package.add_import("/Script/CoreUObject", "Class", core, "ObjectRedirector")
+    # Another package import, the destination is not guessed from the imports
+    material_package = package.add_import("/Script/CoreUObject", "Package", 0, "/Game/Materials/M_Preview")
+    package.add_import("/Script/Engine", "Material", material_package, "M_Preview")
+    destination = package.add_import("/Script/CoreUObject", "Package", 0, destination_package)
+    destination_object = package.add_import(*destination_class, destination, destination_package.rsplit("/", 1)[-1])
+    data = package.name("None") + i32(0) + i32(destination_object)  # Properties, bHasGuid and DestinationObject
+    package.add_export(redirector_class, 0, object_name, data=data)
+    return package
+
+
+def world(map_name, actors, streaming_levels=(), level_script=False):
+    # The actors are exported by the PersistentLevel of the World, its streaming levels are exported by the World
+    package = Package(UE5_4)
+    engine = package.add_import("/Script/CoreUObject", "Package", 0, "/Script/Engine")
+    classes = {}
+
+    def class_import(class_name):
+        if class_name not in classes:
+            classes[class_name] = package.add_import("/Script/CoreUObject", "Class", engine, class_name)
+        return classes[class_name]
+
+    world_export = package.add_export(class_import("World"), 0, map_name, is_asset=True)
+    level = package.add_export(class_import("Level"), world_export, "PersistentLevel")
+    package.add_export(class_import("Model"), level, "Model_0")
+    for class_name, actor_name, components in actors:
+        actor = package.add_export(class_import(class_name), level, actor_name)
+        for component_class, component_name in components:
+            package.add_export(class_import(component_class), actor, component_name)
+    if level_script:
+        # The level script blueprint is saved in the level, the class it generates and its default object at the root
+        package.add_export(class_import("LevelScriptBlueprint"), level, map_name)
+        generated_class = package.add_export(class_import("BlueprintGeneratedClass"), 0, f"{map_name}_C")
+        default_object = package.add_export(
+            generated_class, 0, f"Default__{map_name}_C", flags=RF_PUBLIC | RF_CLASS_DEFAULT_OBJECT | RF_ARCHETYPE_OBJECT
+        )
+        package.add_export(generated_class, level, f"{map_name}_C_0", template=default_object)
+    for index, level_package in enumerate(streaming_levels):
+        package.add_export(class_import("LevelStreamingAlwaysLoaded"), world_export, f"LevelStreamingAlwaysLoaded_{index}")
+        package.soft_object_paths.append((level_package, level_package.rsplit("/", 1)[-1], ""))
+    return package
+
+
+def external_actor(map_package, class_name, actor_name, data_layers=()):
+    # One File Per Actor: the actor is exported in the PersistentLevel imported from the map
+    package = Package(UE5_4)
+    engine = package.add_import("/Script/CoreUObject", "Package", 0, "/Script/Engine")
+    actor_class = package.add_import("/Script/CoreUObject", "Class", engine, class_name)
+    component_class = package.add_import("/Script/CoreUObject", "Class", engine, "StaticMeshComponent")
+    map_import = package.add_import("/Script/CoreUObject", "Package", 0, map_package)
+    world_import = package.add_import("/Script/Engine", "World", map_import, map_package.rsplit("/", 1)[-1])
+    level = package.add_import("/Script/Engine", "Level", world_import, "PersistentLevel")
+    for data_layer in data_layers:
+        data_layer_package = package.add_import("/Script/CoreUObject", "Package", 0, data_layer)
+        package.add_import("/Script/Engine", "DataLayerAsset", data_layer_package, data_layer.rsplit("/", 1)[-1])
+    actor = package.add_export(actor_class, level, actor_name, is_asset=True)
+    package.add_export(component_class, actor, "StaticMeshComponent0")
+    return package
+
+
+if __name__ == "__main__":
+    save("Meshes/SM_Rock.uasset", static_mesh())
+    save("Blueprints/BP_Door.uasset", blueprint())
+    save("Legacy/SM_Crate.uasset", legacy_static_mesh())
+    save("Legacy/SM_Boulder.uasset", redirector("SM_Boulder", "/Game/Meshes/SM_Rock", ("/Script/Engine", "StaticMesh")))
+    save(
+        "Maps/Main.umap",
+        world(
+            "Main",
+            [
+                ("WorldSettings", "WorldSettings", []),
+                ("StaticMeshActor", "StaticMeshActor_0", [("StaticMeshComponent", "StaticMeshComponent0")]),
+                ("PlayerStart", "PlayerStart", []),
+                # The folders of the Outliner are saved in the level with the actors
+                ("ActorFolder", "ActorFolder_0", []),
+            ],
+            streaming_levels=["/Game/Maps/Main_Sub"],
+            level_script=True,
+        ),
+    )
+    save("Maps/Main_Sub.umap", world("Main_Sub", [("WorldSettings", "WorldSettings", []), ("PointLight", "PointLight_0", [])]))
+    save(
+        "__ExternalActors__/Maps/Main/0/AB/4TUV9XJ8KZ2M1Q7W3NBC5D.uasset",
+        external_actor(
+            "/Game/Maps/Main",
+            "StaticMeshActor",
+            "StaticMeshActor_UAID_00155DCC5E9E6C4D01_1234567890",
+            data_layers=["/Game/DataLayers/DL_Night"],
+        ),
+    )